@@ -0,0 +1,7 @@
+ALTER TABLE ${futures} MODIFY COLUMN base_expiry Nullable(Date);
+
+ALTER TABLE ${futures} UPDATE base_expiry = NULL WHERE base_expiry = toDate(0) SETTINGS mutations_sync = 2;
+
+ALTER TABLE ${futures_staging} MODIFY COLUMN base_expiry Nullable(Date);
+
+ALTER TABLE ${futures_staging} UPDATE base_expiry = NULL WHERE base_expiry = toDate(0) SETTINGS mutations_sync = 2;
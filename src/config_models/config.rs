@@ -7,9 +7,10 @@ use crate::config_models::{
     clickhouse_config::ReadClickHouseConfig,
     redis_config::RedisConfigData,
     kafka_config::KafkaConfig,
-    zerodha_config::ZerodhaConfig,
+    zerodha_config::ZerodhaConfigData,
     server_config::ServersConfig,
     ssl_config::SslConfig,
+    index_alias_config::IndexAliasConfig,
     validation::ConfigValidator,  // ✅ Added ConfigValidator import
 };
 
@@ -21,9 +22,10 @@ pub struct Config {
     pub clickhouse: Option<ReadClickHouseConfig>,
     pub redis: Option<RedisConfigData>,
     pub kafka: Option<KafkaConfig>,
-    pub zerodha: Option<ZerodhaConfig>,
+    pub zerodha: Option<ZerodhaConfigData>,
     pub servers: Option<ServersConfig>,
     pub ssl: Option<SslConfig>,
+    pub index_aliases: Option<IndexAliasConfig>,
 }
 
 impl Config {
@@ -3,9 +3,10 @@ use crate::config_models::{
     clickhouse_config::ReadClickHouseConfig, 
     redis_config::RedisConfigData, 
     kafka_config::KafkaConfig,
-    zerodha_config::ZerodhaConfig,
+    zerodha_config::{ZerodhaConfigData, ZerodhaAccount, AccountRole, DEFAULT_ACCOUNT_ID},
     server_config::{ServersConfig, ServerConfig},
     ssl_config::SslConfig,
+    index_alias_config::IndexAliasConfig,
 };
 
 #[derive(Debug, Serialize)]
@@ -13,9 +14,10 @@ pub struct DefaultConfig {
     pub clickhouse: Option<ReadClickHouseConfig>,
     pub redis: Option<RedisConfigData>,
     pub kafka: Option<KafkaConfig>,
-    pub zerodha: Option<ZerodhaConfig>,
+    pub zerodha: Option<ZerodhaConfigData>,
     pub servers: Option<ServersConfig>,
     pub ssl: Option<SslConfig>,
+    pub index_aliases: Option<IndexAliasConfig>,
 }
 
 impl Default for DefaultConfig {
@@ -27,6 +29,7 @@ impl Default for DefaultConfig {
             zerodha: Some(Self::default_zerodha()),
             servers: Some(Self::default_servers()), // ✅ Added default servers
             ssl: Some(Self::default_ssl()), // ✅ Added default SSL
+            index_aliases: None,
         }
     }
 }
@@ -65,11 +68,16 @@ impl DefaultConfig {
         }
     }
 
-    pub fn default_zerodha() -> ZerodhaConfig {
-        ZerodhaConfig {
-            api_key: "API_KEY".to_string(),
-            api_secret: "API_SECRET".to_string(),
-            user_name: "USER_NAME".to_string(),
+    pub fn default_zerodha() -> ZerodhaConfigData {
+        ZerodhaConfigData {
+            default_account: Some(DEFAULT_ACCOUNT_ID.to_string()),
+            accounts: vec![ZerodhaAccount {
+                id: DEFAULT_ACCOUNT_ID.to_string(),
+                api_key: "API_KEY".to_string(),
+                api_secret: "API_SECRET".to_string(),
+                user_name: "USER_NAME".to_string(),
+                role: AccountRole::Trading,
+            }],
         }
     }
 
@@ -95,12 +95,18 @@ impl std::error::Error for RedisError {}
 #[derive(Debug)]
 pub enum ZerodhaConfigError {
     MissingCredentials,
+    UnknownAccount(String),
+    NoAccountForRole(String),
+    DuplicateAccount(String),
 }
 
 impl std::fmt::Display for ZerodhaConfigError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
             ZerodhaConfigError::MissingCredentials => write!(f, "Zerodha credentials are missing"),
+            ZerodhaConfigError::UnknownAccount(id) => write!(f, "Unknown Zerodha account: {}", id),
+            ZerodhaConfigError::NoAccountForRole(role) => write!(f, "No Zerodha account with role: {}", role),
+            ZerodhaConfigError::DuplicateAccount(id) => write!(f, "Duplicate Zerodha account id: {}", id),
         }
     }
 }
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// **Stores the optional `[index_aliases]` section in `config.toml`**
+///
+/// ```toml
+/// [index_aliases]
+/// file = "/etc/project/index_aliases.toml"
+///
+/// [index_aliases.aliases]
+/// "NIFTY IT" = "NIFTYIT"
+/// ```
+///
+/// Entries in `aliases` override those read from `file`, which override the built-in ones.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct IndexAliasConfig {
+    pub file: Option<String>,
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
+}
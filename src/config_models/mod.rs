@@ -8,15 +8,17 @@ pub mod kafka_config;
 pub mod zerodha_config;
 pub mod server_config;
 pub mod ssl_config;
+pub mod index_alias_config;
 
 
 pub use config::Config;
 pub use validation::ConfigValidator;
-pub use error::{ConfigError, ClickHouseError, RedisError, KafkaError};
+pub use error::{ConfigError, ClickHouseError, RedisError, KafkaError, ZerodhaConfigError};
 pub use clickhouse_config::ClickHouseConfig;
 pub use redis_config::{RedisConfig, RedisConfigData, RedisDBType, RedisConnType};
 pub use kafka_config::KafkaConfig;
-pub use zerodha_config::ZerodhaConfig;
+pub use zerodha_config::{ZerodhaConfig, ZerodhaConfigData, ZerodhaAccount, AccountRole, AccountSelector};
 pub use server_config::ServerConfig;
 pub use ssl_config::SslConfig;
+pub use index_alias_config::IndexAliasConfig;
 
@@ -1,17 +1,171 @@
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use crate::config_models::error::ZerodhaConfigError;
 
+/// Account id given to a legacy single-account `[zerodha]` section. That account always gets the
+/// `trading` role; a data account must be listed under `[[zerodha.accounts]]` with `role = "data"`.
+pub const DEFAULT_ACCOUNT_ID: &str = "default";
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum AccountRole {
+    /// Market data / research account.
+    Data,
+    #[default]
+    Trading,
+}
+
+impl AccountRole {
+    /// The spelling used for `role` in `config.toml`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccountRole::Data => "data",
+            AccountRole::Trading => "trading",
+        }
+    }
+}
+
+impl fmt::Display for AccountRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A single Kite account inside `[[zerodha.accounts]]`.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ZerodhaAccount {
+    pub id: String,
+    pub api_key: String,
+    pub api_secret: String,
+    pub user_name: String,
+    #[serde(default)]
+    pub role: AccountRole,
+}
+
+/// **Stores the `zerodha` section in `config.toml`**
+/// Accepts either a list of `accounts` or the legacy flat `api_key`/`api_secret`/`user_name` form.
+/// A legacy section becomes one account with id [`DEFAULT_ACCOUNT_ID`] and the `trading` role.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(try_from = "ZerodhaSection")]
+pub struct ZerodhaConfigData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_account: Option<String>,
+    pub accounts: Vec<ZerodhaAccount>,
+}
+
+/// Both forms of the section. When `accounts` is present it is parsed as the list form, so a
+/// typo inside an account reports that account's error rather than a generic mismatch.
+#[derive(Deserialize)]
+struct ZerodhaSection {
+    default_account: Option<String>,
+    accounts: Option<Vec<ZerodhaAccount>>,
+    api_key: Option<String>,
+    api_secret: Option<String>,
+    user_name: Option<String>,
+}
+
+impl TryFrom<ZerodhaSection> for ZerodhaConfigData {
+    type Error = String;
+
+    fn try_from(section: ZerodhaSection) -> Result<Self, Self::Error> {
+        if let Some(accounts) = section.accounts {
+            return Ok(Self {
+                default_account: section.default_account,
+                accounts,
+            });
+        }
+
+        let missing = |field: &str| format!("missing field `{}` (or a list of `accounts`)", field);
+        Ok(Self {
+            default_account: None,
+            accounts: vec![ZerodhaAccount {
+                id: DEFAULT_ACCOUNT_ID.to_string(),
+                api_key: section.api_key.ok_or_else(|| missing("api_key"))?,
+                api_secret: section.api_secret.ok_or_else(|| missing("api_secret"))?,
+                user_name: section.user_name.ok_or_else(|| missing("user_name"))?,
+                role: AccountRole::Trading,
+            }],
+        })
+    }
+}
+
+/// Which configured account to load.
+#[derive(Clone, Debug)]
+pub enum AccountSelector {
+    /// `default_account` if set, otherwise the first account.
+    Default,
+    Id(String),
+    /// The first account with the given role.
+    Role(AccountRole),
+}
+
+/// Credentials of the selected Kite account.
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct ZerodhaConfig {
+    pub account_id: String,
     pub api_key: String,
     pub api_secret: String,
     pub user_name: String,
+    pub role: AccountRole,
 }
 
 impl ZerodhaConfig {
-    /// Loads the Zerodha configuration from the centralized `Config`.
-    pub fn from_config(config: &crate::config_models::Config) -> Result<Self, ZerodhaConfigError> {
-        config.zerodha.clone().ok_or(ZerodhaConfigError::MissingCredentials)
+    /// Loads the selected Zerodha account from the centralized `Config`.
+    pub fn from_config(config: &crate::config_models::Config, selector: AccountSelector) -> Result<Self, ZerodhaConfigError> {
+        let zerodha = config.zerodha.as_ref().ok_or(ZerodhaConfigError::MissingCredentials)?;
+        zerodha.validate()?;
+
+        let account = match selector {
+            AccountSelector::Default => match &zerodha.default_account {
+                Some(id) => zerodha.account(id),
+                None => zerodha.accounts.first().ok_or(ZerodhaConfigError::MissingCredentials),
+            },
+            AccountSelector::Id(id) => zerodha.account(&id),
+            AccountSelector::Role(role) => zerodha
+                .accounts
+                .iter()
+                .find(|account| account.role == role)
+                .ok_or_else(|| ZerodhaConfigError::NoAccountForRole(role.to_string())),
+        }?;
+
+        Ok(Self::from(account))
+    }
+}
+
+impl From<&ZerodhaAccount> for ZerodhaConfig {
+    fn from(account: &ZerodhaAccount) -> Self {
+        Self {
+            account_id: account.id.clone(),
+            api_key: account.api_key.clone(),
+            api_secret: account.api_secret.clone(),
+            user_name: account.user_name.clone(),
+            role: account.role,
+        }
     }
 }
 
+impl ZerodhaConfigData {
+    /// Looks up an account by id.
+    pub fn account(&self, id: &str) -> Result<&ZerodhaAccount, ZerodhaConfigError> {
+        self.accounts
+            .iter()
+            .find(|account| account.id == id)
+            .ok_or_else(|| ZerodhaConfigError::UnknownAccount(id.to_string()))
+    }
+
+    /// Ensures there is at least one account and that ids are unique.
+    pub fn validate(&self) -> Result<(), ZerodhaConfigError> {
+        if self.accounts.is_empty() {
+            return Err(ZerodhaConfigError::MissingCredentials);
+        }
+        for (i, account) in self.accounts.iter().enumerate() {
+            if self.accounts[..i].iter().any(|other| other.id == account.id) {
+                return Err(ZerodhaConfigError::DuplicateAccount(account.id.clone()));
+            }
+        }
+        if let Some(id) = &self.default_account {
+            self.account(id)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,30 @@
+use std::fmt;
+
+/// **Trading Calendar Errors**
+#[derive(Debug, Clone, PartialEq)]
+pub enum CalendarError {
+    /// The calendar file could not be read.
+    Io(String),
+    /// The calendar file is not valid TOML or has the wrong shape.
+    Parse(String),
+    InvalidDate(String),
+    InvalidTime(String),
+    /// A session that closes at or before it opens.
+    InvalidSession { open: String, close: String },
+}
+
+impl fmt::Display for CalendarError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CalendarError::Io(msg) => write!(f, "Calendar file error: {}", msg),
+            CalendarError::Parse(msg) => write!(f, "Invalid calendar file: {}", msg),
+            CalendarError::InvalidDate(value) => write!(f, "Invalid calendar date: {}", value),
+            CalendarError::InvalidTime(value) => write!(f, "Invalid session time: {}", value),
+            CalendarError::InvalidSession { open, close } => {
+                write!(f, "Session closes at {} before it opens at {}", close, open)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CalendarError {}
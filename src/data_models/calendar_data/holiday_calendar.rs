@@ -0,0 +1,43 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+use std::collections::BTreeSet;
+
+/// Exchange trading days: weekdays that are not listed holidays.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HolidayCalendar {
+    holidays: BTreeSet<NaiveDate>,
+}
+
+impl HolidayCalendar {
+    pub fn new<I: IntoIterator<Item = NaiveDate>>(holidays: I) -> Self {
+        Self {
+            holidays: holidays.into_iter().collect(),
+        }
+    }
+
+    pub fn add_holiday(&mut self, date: NaiveDate) {
+        self.holidays.insert(date);
+    }
+
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.holidays.contains(&date)
+    }
+
+    pub fn is_trading_day(&self, date: NaiveDate) -> bool {
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && !self.is_holiday(date)
+    }
+
+    /// Trading days after `from` up to and including `to`; zero if `to` is not after `from`.
+    pub fn trading_days_between(&self, from: NaiveDate, to: NaiveDate) -> u32 {
+        from.iter_days()
+            .skip(1)
+            .take_while(|date| *date <= to)
+            .filter(|date| self.is_trading_day(*date))
+            .count() as u32
+    }
+}
+
+impl FromIterator<NaiveDate> for HolidayCalendar {
+    fn from_iter<I: IntoIterator<Item = NaiveDate>>(iter: I) -> Self {
+        Self::new(iter)
+    }
+}
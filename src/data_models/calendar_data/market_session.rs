@@ -0,0 +1,51 @@
+use crate::data_models::calendar_data::CalendarError;
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionKind {
+    PreOpen,
+    Normal,
+    /// MCX evening session.
+    Evening,
+    /// Diwali Muhurat trading.
+    Muhurat,
+    /// Any other one-off session, e.g. a Saturday DR-site mock session.
+    Special,
+}
+
+/// A trading window in exchange local time (IST). `close` is exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarketSession {
+    pub kind: SessionKind,
+    pub open: NaiveTime,
+    pub close: NaiveTime,
+}
+
+impl MarketSession {
+    pub fn new(kind: SessionKind, open: NaiveTime, close: NaiveTime) -> Result<Self, CalendarError> {
+        if close <= open {
+            return Err(CalendarError::InvalidSession {
+                open: open.to_string(),
+                close: close.to_string(),
+            });
+        }
+        Ok(Self { kind, open, close })
+    }
+
+    /// Builds a session from `HH:MM` times.
+    pub fn parse(kind: SessionKind, open: &str, close: &str) -> Result<Self, CalendarError> {
+        Self::new(kind, parse_time(open)?, parse_time(close)?)
+    }
+
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        self.open <= time && time < self.close
+    }
+}
+
+fn parse_time(value: &str) -> Result<NaiveTime, CalendarError> {
+    NaiveTime::parse_from_str(value, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(value, "%H:%M:%S"))
+        .map_err(|_| CalendarError::InvalidTime(value.to_string()))
+}
@@ -0,0 +1,9 @@
+mod holiday_calendar;
+mod market_session;
+mod trading_calendar;
+mod error;
+
+pub use holiday_calendar::*;
+pub use market_session::*;
+pub use trading_calendar::*;
+pub use error::*;
@@ -0,0 +1,238 @@
+use crate::data_models::calendar_data::{CalendarError, HolidayCalendar, MarketSession, SessionKind};
+use crate::data_models::instrument_data::BaseExchange;
+use chrono::{Days, NaiveDate, NaiveDateTime};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Bound;
+use std::fs;
+use std::path::Path;
+
+/// How far `next_session` and `previous_trading_day` search, e.g. for an exchange with no sessions.
+const MAX_LOOKAHEAD_DAYS: u64 = 366;
+
+#[derive(Debug, Deserialize)]
+struct SessionEntry {
+    kind: SessionKind,
+    open: String,
+    close: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpecialSessionEntry {
+    date: String,
+    #[serde(default = "default_special_kind")]
+    kind: SessionKind,
+    open: String,
+    close: String,
+}
+
+fn default_special_kind() -> SessionKind {
+    SessionKind::Special
+}
+
+/// One exchange's table in the calendar file. Omitted `sessions` keep the built-in timings.
+#[derive(Debug, Default, Deserialize)]
+struct ExchangeEntry {
+    #[serde(default)]
+    holidays: Vec<String>,
+    #[serde(default)]
+    special_sessions: Vec<SpecialSessionEntry>,
+    sessions: Option<Vec<SessionEntry>>,
+}
+
+/// Trading days and market hours for one `BaseExchange`, in IST.
+///
+/// Calendar files are TOML with a table per exchange:
+///
+/// ```toml
+/// [NSE]
+/// holidays = ["2024-12-25"]
+///
+/// [[NSE.special_sessions]]
+/// date = "2024-11-01"
+/// kind = "muhurat"
+/// open = "18:00"
+/// close = "19:00"
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradingCalendar {
+    base_exchange: BaseExchange,
+    sessions: Vec<MarketSession>,
+    holidays: HolidayCalendar,
+    special_sessions: BTreeMap<NaiveDate, Vec<MarketSession>>,
+}
+
+impl TradingCalendar {
+    /// Built-in session timings with no holidays.
+    pub fn new(base_exchange: BaseExchange) -> Self {
+        Self {
+            base_exchange,
+            sessions: default_sessions(base_exchange),
+            holidays: HolidayCalendar::default(),
+            special_sessions: BTreeMap::new(),
+        }
+    }
+
+    /// Reads the calendars for every exchange in the file. Exchanges it doesn't mention are omitted.
+    pub fn load_all<P: AsRef<Path>>(path: P) -> Result<HashMap<BaseExchange, TradingCalendar>, CalendarError> {
+        let content = fs::read_to_string(path.as_ref())
+            .map_err(|e| CalendarError::Io(format!("Failed to read {}: {}", path.as_ref().display(), e)))?;
+        Self::parse_all(&content)
+    }
+
+    /// Reads the calendar for `base_exchange`, falling back to built-in timings if the file has no table for it.
+    pub fn load<P: AsRef<Path>>(path: P, base_exchange: BaseExchange) -> Result<Self, CalendarError> {
+        Ok(Self::load_all(path)?
+            .remove(&base_exchange)
+            .unwrap_or_else(|| Self::new(base_exchange)))
+    }
+
+    pub fn parse_all(content: &str) -> Result<HashMap<BaseExchange, TradingCalendar>, CalendarError> {
+        let entries: HashMap<String, ExchangeEntry> =
+            toml::from_str(content).map_err(|e| CalendarError::Parse(e.to_string()))?;
+
+        let mut calendars = HashMap::new();
+        for (name, entry) in entries {
+            let base_exchange = name
+                .parse::<BaseExchange>()
+                .map_err(|e| CalendarError::Parse(e.to_string()))?;
+            calendars.insert(base_exchange, Self::from_entry(base_exchange, entry)?);
+        }
+        Ok(calendars)
+    }
+
+    fn from_entry(base_exchange: BaseExchange, entry: ExchangeEntry) -> Result<Self, CalendarError> {
+        let mut calendar = Self::new(base_exchange);
+        if let Some(sessions) = entry.sessions {
+            calendar.sessions = sessions
+                .iter()
+                .map(|s| MarketSession::parse(s.kind, &s.open, &s.close))
+                .collect::<Result<_, _>>()?;
+        }
+        for holiday in &entry.holidays {
+            calendar.add_holiday(parse_date(holiday)?);
+        }
+        for special in &entry.special_sessions {
+            let session = MarketSession::parse(special.kind, &special.open, &special.close)?;
+            calendar.add_special_session(parse_date(&special.date)?, session);
+        }
+        Ok(calendar)
+    }
+
+    /// Replaces the regular daily sessions.
+    pub fn with_sessions(mut self, sessions: Vec<MarketSession>) -> Self {
+        self.sessions = sessions;
+        self
+    }
+
+    pub fn add_holiday(&mut self, date: NaiveDate) {
+        self.holidays.add_holiday(date);
+    }
+
+    /// Adds a one-off session, e.g. Muhurat trading. It runs even if the date is a holiday or weekend.
+    pub fn add_special_session(&mut self, date: NaiveDate, session: MarketSession) {
+        let sessions = self.special_sessions.entry(date).or_default();
+        sessions.push(session);
+        sessions.sort_by_key(|s| s.open);
+    }
+
+    pub fn base_exchange(&self) -> BaseExchange {
+        self.base_exchange
+    }
+
+    /// Regular daily sessions, earliest first.
+    pub fn sessions(&self) -> &[MarketSession] {
+        &self.sessions
+    }
+
+    pub fn holidays(&self) -> &HolidayCalendar {
+        &self.holidays
+    }
+
+    fn is_regular_day(&self, date: NaiveDate) -> bool {
+        self.holidays.is_trading_day(date)
+    }
+
+    /// A weekday that is not a holiday, or any day with a special session.
+    pub fn is_trading_day(&self, date: NaiveDate) -> bool {
+        self.is_regular_day(date) || self.special_sessions.contains_key(&date)
+    }
+
+    /// Every session held on `date`, earliest first.
+    pub fn sessions_on(&self, date: NaiveDate) -> Vec<MarketSession> {
+        let mut sessions = Vec::new();
+        if self.is_regular_day(date) {
+            sessions.extend_from_slice(&self.sessions);
+        }
+        if let Some(special) = self.special_sessions.get(&date) {
+            sessions.extend_from_slice(special);
+        }
+        sessions.sort_by_key(|s| s.open);
+        sessions
+    }
+
+    /// The session in progress at `at`, if any.
+    pub fn session_at(&self, at: NaiveDateTime) -> Option<MarketSession> {
+        self.sessions_on(at.date())
+            .into_iter()
+            .find(|s| s.contains(at.time()))
+    }
+
+    pub fn is_open(&self, at: NaiveDateTime) -> bool {
+        self.session_at(at).is_some()
+    }
+
+    /// The first session opening at or after `after`, with its date.
+    pub fn next_session(&self, after: NaiveDateTime) -> Option<(NaiveDate, MarketSession)> {
+        after
+            .date()
+            .iter_days()
+            .take(MAX_LOOKAHEAD_DAYS as usize)
+            .flat_map(|date| self.sessions_on(date).into_iter().map(move |s| (date, s)))
+            .find(|(date, s)| date.and_time(s.open) >= after)
+    }
+
+    pub fn next_session_open(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        self.next_session(after).map(|(date, s)| date.and_time(s.open))
+    }
+
+    /// Trading days after `from` up to and including `to`, counting special-session days.
+    pub fn trading_days_between(&self, from: NaiveDate, to: NaiveDate) -> u32 {
+        if to <= from {
+            return 0;
+        }
+        let special_only = self
+            .special_sessions
+            .range((Bound::Excluded(from), Bound::Included(to)))
+            .filter(|(date, _)| !self.is_regular_day(**date))
+            .count() as u32;
+        self.holidays.trading_days_between(from, to) + special_only
+    }
+
+    /// The last trading day strictly before `date`.
+    pub fn previous_trading_day(&self, date: NaiveDate) -> Option<NaiveDate> {
+        (1..=MAX_LOOKAHEAD_DAYS)
+            .filter_map(|n| date.checked_sub_days(Days::new(n)))
+            .find(|d| self.is_trading_day(*d))
+    }
+}
+
+/// Exchange hours as of 2024. NSEIX and GLOBAL have no built-in sessions.
+fn default_sessions(base_exchange: BaseExchange) -> Vec<MarketSession> {
+    let session = |kind, open, close| MarketSession::parse(kind, open, close).expect("valid built-in session");
+    match base_exchange {
+        BaseExchange::Nse | BaseExchange::Bse => vec![
+            session(SessionKind::PreOpen, "09:00", "09:15"),
+            session(SessionKind::Normal, "09:15", "15:30"),
+        ],
+        BaseExchange::Mcx => vec![
+            session(SessionKind::Normal, "09:00", "17:00"),
+            session(SessionKind::Evening, "17:00", "23:30"),
+        ],
+        BaseExchange::Nseix | BaseExchange::Global => Vec::new(),
+    }
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, CalendarError> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| CalendarError::InvalidDate(value.to_string()))
+}
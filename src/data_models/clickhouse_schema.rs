@@ -0,0 +1,253 @@
+use crate::config_models::ClickHouseConfig;
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// Database and table-name prefix that DDL and queries are built for. A per-run prefix or
+/// throwaway database keeps test schemas apart from real ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaTarget {
+    database: String,
+    prefix: String,
+}
+
+impl SchemaTarget {
+    pub fn new(database: impl Into<String>) -> Self {
+        Self {
+            database: database.into(),
+            prefix: String::new(),
+        }
+    }
+
+    pub fn from_config(config: &ClickHouseConfig) -> Self {
+        Self::new(config.database.clone())
+    }
+
+    /// Prepends `prefix` to every table name, e.g. `test_42_` → `test_42_futures`.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    pub fn database(&self) -> &str {
+        &self.database
+    }
+
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Prefixed table name without the database, as `system.tables` lists it.
+    pub fn table_name(&self, table: &str) -> String {
+        format!("{}{}", self.prefix, table)
+    }
+
+    /// Fully qualified table name, e.g. `market.futures`.
+    pub fn table(&self, table: &str) -> String {
+        format!("{}.{}", self.database, self.table_name(table))
+    }
+
+    /// Replaces each `${table}` placeholder in `sql` with the qualified table name.
+    pub fn render(&self, sql: &str) -> String {
+        let mut rendered = String::with_capacity(sql.len());
+        let mut rest = sql;
+        while let Some(start) = rest.find("${") {
+            let Some(len) = rest[start + 2..].find('}') else {
+                break;
+            };
+            rendered.push_str(&rest[..start]);
+            rendered.push_str(&self.table(&rest[start + 2..start + 2 + len]));
+            rest = &rest[start + 2 + len + 1..];
+        }
+        rendered.push_str(rest);
+        rendered
+    }
+}
+
+/// ClickHouse column type of a Rust field type.
+pub trait ClickHouseType {
+    fn clickhouse_type() -> String;
+}
+
+macro_rules! impl_clickhouse_type {
+    ($($ty:ty => $name:literal),+ $(,)?) => {
+        $(
+            impl ClickHouseType for $ty {
+                fn clickhouse_type() -> String {
+                    $name.to_string()
+                }
+            }
+        )+
+    };
+}
+
+impl_clickhouse_type!(
+    String => "String",
+    bool => "Bool",
+    u8 => "UInt8",
+    u16 => "UInt16",
+    u32 => "UInt32",
+    u64 => "UInt64",
+    i8 => "Int8",
+    i16 => "Int16",
+    i32 => "Int32",
+    i64 => "Int64",
+    f32 => "Float32",
+    f64 => "Float64",
+    NaiveDate => "Date",
+    // Exchange timestamps are shown in IST; storage is a Unix timestamp either way.
+    DateTime<Utc> => "DateTime('Asia/Kolkata')",
+);
+
+impl<T: ClickHouseType> ClickHouseType for Option<T> {
+    fn clickhouse_type() -> String {
+        format!("Nullable({})", T::clickhouse_type())
+    }
+}
+
+impl<T: ClickHouseType> ClickHouseType for Vec<T> {
+    fn clickhouse_type() -> String {
+        format!("Array({})", T::clickhouse_type())
+    }
+}
+
+/// A `#[repr(i8)]` enum stored as `Enum8`. Implemented with `clickhouse_enum!`.
+pub trait ClickHouseEnum: Sized {
+    /// Name of the variant in the `Enum8` definition.
+    fn clickhouse_name(&self) -> &'static str;
+
+    /// Every variant, in declaration order.
+    fn variants() -> Vec<Self>;
+
+    fn discriminant(&self) -> i8;
+
+    /// e.g. `Enum8('Day' = 0, 'Minute' = 1)`.
+    fn enum8() -> String {
+        let values: Vec<String> = Self::variants()
+            .iter()
+            .map(|v| format!("'{}' = {}", v.clickhouse_name(), v.discriminant()))
+            .collect();
+        format!("Enum8({})", values.join(", "))
+    }
+}
+
+/// A row type with a table definition. Implemented with `clickhouse_table!`.
+pub trait ClickHouseTable {
+    /// Unqualified table name; `SchemaTarget` supplies the database and prefix.
+    const TABLE: &'static str;
+    const ENGINE: &'static str;
+    const PARTITION_BY: Option<&'static str>;
+    const ORDER_BY: &'static str;
+
+    /// `(name, type)` for each field, in declaration order.
+    fn columns() -> Vec<(&'static str, String)>;
+
+    fn create_table() -> String {
+        Self::create_table_named(Self::TABLE)
+    }
+
+    fn create_table_in(target: &SchemaTarget) -> String {
+        Self::create_table_named(&target.table(Self::TABLE))
+    }
+
+    /// The same definition under another name, e.g. a staging table.
+    fn create_table_named(table: &str) -> String {
+        let columns: Vec<String> = Self::columns()
+            .into_iter()
+            .map(|(name, ty)| format!("    {} {}", name, ty))
+            .collect();
+
+        let mut sql = format!(
+            "CREATE TABLE IF NOT EXISTS {}\n(\n{}\n)\nENGINE = {}\n",
+            table,
+            columns.join(",\n"),
+            Self::ENGINE
+        );
+        if let Some(partition_by) = Self::PARTITION_BY {
+            sql.push_str(&format!("PARTITION BY {}\n", partition_by));
+        }
+        sql.push_str(&format!("ORDER BY {}", Self::ORDER_BY));
+        sql
+    }
+}
+
+/// Implements `ClickHouseEnum` and `ClickHouseType` for a `#[repr(i8)]` enum. Variants are
+/// named after themselves unless given a name, e.g. `Atm => "atm"`. Every variant must be
+/// listed, or `clickhouse_name` fails to compile.
+macro_rules! clickhouse_enum {
+    (@name $variant:ident $name:literal) => { $name };
+    (@name $variant:ident) => { stringify!($variant) };
+
+    ($ty:ident { $($variant:ident $(=> $name:literal)?),+ $(,)? }) => {
+        impl $crate::data_models::ClickHouseEnum for $ty {
+            fn clickhouse_name(&self) -> &'static str {
+                match self {
+                    $($ty::$variant => $crate::data_models::clickhouse_enum!(@name $variant $($name)?),)+
+                }
+            }
+
+            fn variants() -> Vec<Self> {
+                vec![$($ty::$variant),+]
+            }
+
+            fn discriminant(&self) -> i8 {
+                match self {
+                    $($ty::$variant => $ty::$variant as i8,)+
+                }
+            }
+        }
+
+        impl $crate::data_models::ClickHouseType for $ty {
+            fn clickhouse_type() -> String {
+                <$ty as $crate::data_models::ClickHouseEnum>::enum8()
+            }
+        }
+    };
+}
+
+/// Declares a row struct and implements `ClickHouseTable` from its fields, so the DDL
+/// always matches the type. Column names are the field names; don't `#[serde(rename)]` fields.
+///
+/// ```ignore
+/// clickhouse_table! {
+///     #[clickhouse_table(name = "futures", engine = "MergeTree()", order_by = "(name, expiry)")]
+///     #[derive(clickhouse::Row, serde::Serialize, serde::Deserialize)]
+///     pub struct Row { pub name: String, ... }
+/// }
+/// ```
+macro_rules! clickhouse_table {
+    (@option $value:literal) => { Some($value) };
+    (@option) => { None };
+
+    (
+        #[clickhouse_table(
+            name = $table:literal,
+            engine = $engine:literal,
+            $(partition_by = $partition:literal,)?
+            order_by = $order:literal $(,)?
+        )]
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident {
+            $($(#[$field_attr:meta])* $field_vis:vis $field:ident : $field_ty:ty),+ $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        $vis struct $name {
+            $($(#[$field_attr])* $field_vis $field: $field_ty,)+
+        }
+
+        impl $crate::data_models::ClickHouseTable for $name {
+            const TABLE: &'static str = $table;
+            const ENGINE: &'static str = $engine;
+            const PARTITION_BY: Option<&'static str> = $crate::data_models::clickhouse_table!(@option $($partition)?);
+            const ORDER_BY: &'static str = $order;
+
+            fn columns() -> Vec<(&'static str, String)> {
+                vec![
+                    $((stringify!($field), <$field_ty as $crate::data_models::ClickHouseType>::clickhouse_type()),)+
+                ]
+            }
+        }
+    };
+}
+
+pub(crate) use clickhouse_enum;
+pub(crate) use clickhouse_table;
@@ -0,0 +1,290 @@
+use crate::data_models::calendar_data::TradingCalendar;
+use crate::data_models::futures_data::{ExpiryCalendar, FuturesData};
+use crate::data_models::hist_data::HistData;
+use crate::data_models::instrument_data::BaseExchange;
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::HashMap;
+
+/// When the series moves from the front contract to the next expiry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RollRule {
+    /// On the first candle at most this many trading days before the front contract's expiry.
+    /// `DaysBeforeExpiry(0)` rolls on expiry day.
+    DaysBeforeExpiry(u32),
+    /// On the first candle where the next contract trades more volume than the front one.
+    /// `HistData` has no open interest, so volume stands in for it.
+    VolumeCrossover,
+}
+
+/// How earlier contracts' prices are adjusted at each roll. Adjustments are applied backwards,
+/// so the latest contract keeps its traded prices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Adjustment {
+    /// Raw prices, with gaps at the rolls.
+    None,
+    /// Adds the price gap at each roll to everything before it.
+    Difference,
+    /// Scales everything before a roll by the price ratio at it.
+    Ratio,
+}
+
+/// One switch from a contract to the next expiry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Roll {
+    /// First candle taken from the new contract.
+    pub datetime: DateTime<Utc>,
+    pub from_expiry: NaiveDate,
+    pub to_expiry: NaiveDate,
+    pub from_tradingsymbol: String,
+    pub to_tradingsymbol: String,
+    /// Close of the old contract's last candle before the roll.
+    pub from_price: f64,
+    /// The new contract's close at that same candle, or its open at the roll if it didn't trade then.
+    pub to_price: f64,
+}
+
+impl Roll {
+    pub fn difference(&self) -> f64 {
+        self.to_price - self.from_price
+    }
+
+    /// 1 if the old price is zero.
+    pub fn ratio(&self) -> f64 {
+        if self.from_price == 0.0 {
+            1.0
+        } else {
+            self.to_price / self.from_price
+        }
+    }
+}
+
+/// A candle of the continuous series and the contract it came from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContinuousCandle {
+    pub datetime: DateTime<Utc>,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: u64,
+    pub tradingsymbol: String,
+    pub expiry: NaiveDate,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContinuousSeries {
+    pub base_exchange: BaseExchange,
+    pub name: String,
+    pub candles: Vec<ContinuousCandle>,
+    pub rolls: Vec<Roll>,
+}
+
+impl ContinuousSeries {
+    /// Dates the series switched contracts.
+    pub fn roll_dates(&self) -> Vec<NaiveDate> {
+        self.rolls.iter().map(|r| r.datetime.date_naive()).collect()
+    }
+}
+
+/// Stitches per-contract candles into a front-month series.
+///
+/// ```ignore
+/// let series = ContinuousFutures::new(RollRule::DaysBeforeExpiry(2))
+///     .with_adjustment(Adjustment::Difference)
+///     .build(BaseExchange::Nse, "NIFTY", &futures, &candles_by_expiry);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ContinuousFutures {
+    roll: RollRule,
+    adjustment: Adjustment,
+    calendars: HashMap<BaseExchange, TradingCalendar>,
+}
+
+struct Contract<'a> {
+    expiry: NaiveDate,
+    /// Sorted by datetime.
+    candles: Vec<&'a HistData>,
+}
+
+impl<'a> Contract<'a> {
+    /// The candle at exactly `datetime`, if this contract traded then.
+    fn at(&self, datetime: DateTime<Utc>) -> Option<&'a HistData> {
+        let i = self.candles.binary_search_by_key(&datetime, |c| c.datetime).ok()?;
+        Some(self.candles[i])
+    }
+}
+
+impl ContinuousFutures {
+    pub fn new(roll: RollRule) -> Self {
+        Self {
+            roll,
+            adjustment: Adjustment::None,
+            calendars: HashMap::new(),
+        }
+    }
+
+    pub fn with_adjustment(mut self, adjustment: Adjustment) -> Self {
+        self.adjustment = adjustment;
+        self
+    }
+
+    /// The calendar for counting `DaysBeforeExpiry` on its exchange, including special sessions.
+    /// Exchanges without one count weekdays.
+    pub fn with_calendar(mut self, calendar: TradingCalendar) -> Self {
+        self.calendars.insert(calendar.base_exchange(), calendar);
+        self
+    }
+
+    /// Builds the series for `name` on `base_exchange` from the expiries listed in `futures`.
+    /// `candles` holds each contract's candles keyed by expiry; expiries without candles are
+    /// skipped. A contract is always rolled out of once the next one trades after its expiry date.
+    pub fn build(
+        &self,
+        base_exchange: BaseExchange,
+        name: &str,
+        futures: &[FuturesData],
+        candles: &HashMap<NaiveDate, Vec<HistData>>,
+    ) -> ContinuousSeries {
+        let mut expiries: Vec<NaiveDate> = futures
+            .iter()
+            .filter(|f| f.base_exchange == base_exchange && f.name == name)
+            .map(|f| f.expiry)
+            .collect();
+        expiries.sort();
+        expiries.dedup();
+
+        let contracts: Vec<Contract> = expiries
+            .into_iter()
+            .filter_map(|expiry| {
+                let mut candles: Vec<&HistData> = candles.get(&expiry)?.iter().collect();
+                if candles.is_empty() {
+                    return None;
+                }
+                candles.sort_by_key(|c| c.datetime);
+                Some(Contract { expiry, candles })
+            })
+            .collect();
+
+        let weekdays;
+        let calendar = match self.calendars.get(&base_exchange) {
+            Some(calendar) => calendar,
+            None => {
+                weekdays = TradingCalendar::new(base_exchange);
+                &weekdays
+            }
+        };
+
+        let mut segments: Vec<(&Contract, Vec<&HistData>)> = Vec::new();
+        let mut rolls = Vec::new();
+        let mut start: Option<DateTime<Utc>> = None;
+
+        let mut front = if contracts.is_empty() { None } else { Some(0) };
+        while let Some(i) = front {
+            let contract = &contracts[i];
+            let from_start = |c: &&HistData| start.is_none_or(|s| c.datetime >= s);
+            // A later contract with no candle to roll on, e.g. one whose data stops early, is
+            // skipped in favour of the one after it.
+            let roll = contracts
+                .iter()
+                .enumerate()
+                .skip(i + 1)
+                .find_map(|(j, next)| Some((j, self.roll_candle(calendar, contract, next, start)?)));
+
+            let segment: Vec<&HistData> = contract
+                .candles
+                .iter()
+                .copied()
+                .filter(from_start)
+                .filter(|c| roll.is_none_or(|(_, r)| c.datetime < r.datetime))
+                .collect();
+
+            front = roll.map(|(j, _)| j);
+            if let Some((j, roll_candle)) = roll {
+                rolls.push(roll_between(contract, &contracts[j], segment.last().copied(), roll_candle));
+                start = Some(roll_candle.datetime);
+            }
+            segments.push((contract, segment));
+        }
+
+        let candles = self.adjusted(&segments, &rolls);
+        ContinuousSeries {
+            base_exchange,
+            name: name.to_string(),
+            candles,
+            rolls,
+        }
+    }
+
+    /// The first candle of `next`, at or after `start`, on which the series rolls into it.
+    fn roll_candle<'a>(
+        &self,
+        calendar: &TradingCalendar,
+        front: &Contract,
+        next: &Contract<'a>,
+        start: Option<DateTime<Utc>>,
+    ) -> Option<&'a HistData> {
+        next.candles
+            .iter()
+            .copied()
+            .filter(|c| start.is_none_or(|s| c.datetime >= s))
+            .find(|candle| {
+                let date = candle.datetime.date_naive();
+                if date > front.expiry {
+                    return true;
+                }
+                match self.roll {
+                    RollRule::DaysBeforeExpiry(days) => {
+                        ExpiryCalendar::trading_dte(date, front.expiry, calendar) <= days
+                    }
+                    RollRule::VolumeCrossover => {
+                        front.at(candle.datetime).is_some_and(|c| candle.volume > c.volume)
+                    }
+                }
+            })
+    }
+
+    fn adjusted(&self, segments: &[(&Contract, Vec<&HistData>)], rolls: &[Roll]) -> Vec<ContinuousCandle> {
+        let mut candles = Vec::new();
+        for (k, (contract, segment)) in segments.iter().enumerate() {
+            let later = &rolls[k..];
+            let (offset, factor) = match self.adjustment {
+                Adjustment::None => (0.0, 1.0),
+                Adjustment::Difference => (later.iter().map(Roll::difference).sum(), 1.0),
+                Adjustment::Ratio => (0.0, later.iter().map(Roll::ratio).product()),
+            };
+            let price = |p: f64| p * factor + offset;
+
+            candles.extend(segment.iter().map(|c| ContinuousCandle {
+                datetime: c.datetime,
+                open: price(c.open),
+                high: price(c.high),
+                low: price(c.low),
+                close: price(c.close),
+                volume: c.volume,
+                tradingsymbol: c.tradingsymbol.clone(),
+                expiry: contract.expiry,
+            }));
+        }
+        candles
+    }
+}
+
+fn roll_between(front: &Contract, next: &Contract, last: Option<&HistData>, roll_candle: &HistData) -> Roll {
+    let (from_price, to_price) = match last {
+        Some(last) => {
+            let to_price = next.at(last.datetime).map_or(roll_candle.open, |c| c.close);
+            (last.close, to_price)
+        }
+        None => (roll_candle.open, roll_candle.open),
+    };
+
+    Roll {
+        datetime: roll_candle.datetime,
+        from_expiry: front.expiry,
+        to_expiry: next.expiry,
+        from_tradingsymbol: front.candles[0].tradingsymbol.clone(),
+        to_tradingsymbol: roll_candle.tradingsymbol.clone(),
+        from_price,
+        to_price,
+    }
+}
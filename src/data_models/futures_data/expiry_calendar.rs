@@ -0,0 +1,125 @@
+use crate::data_models::calendar_data::TradingCalendar;
+use crate::data_models::instrument_data::Instrument;
+use chrono::{Datelike, NaiveDate};
+use std::collections::BTreeSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExpiryKind {
+    /// Any expiry that is not the last one of its month.
+    Weekly,
+    /// The last expiry of a month in the serial run of consecutive months.
+    Monthly,
+    /// The last expiry of a month listed after the serial monthly run, e.g. far Jun/Sep/Dec contracts.
+    Quarterly,
+}
+
+/// Listed derivative expiries for one underlying, classified as weekly, monthly or quarterly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpiryCalendar {
+    name: String,
+    expiries: Vec<(NaiveDate, ExpiryKind)>,
+}
+
+impl ExpiryCalendar {
+    /// Collects the futures and options expiries of `name` from `instruments`.
+    pub fn from_instruments<'a, I>(name: &str, instruments: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Instrument>,
+    {
+        let expiries = instruments
+            .into_iter()
+            .filter(|i| i.name.as_deref() == Some(name) && i.segment.is_derivative())
+            .filter_map(|i| i.expiry);
+        Self::new(name, expiries)
+    }
+
+    pub fn new<I: IntoIterator<Item = NaiveDate>>(name: &str, expiries: I) -> Self {
+        let dates: Vec<NaiveDate> = expiries.into_iter().collect::<BTreeSet<_>>().into_iter().collect();
+
+        let mut classified = Vec::with_capacity(dates.len());
+        let mut previous_month: Option<(i32, u32)> = None;
+        let mut serial = true;
+        for (i, &date) in dates.iter().enumerate() {
+            let month = (date.year(), date.month());
+            let last_of_month = dates.get(i + 1).is_none_or(|next| (next.year(), next.month()) != month);
+
+            let kind = if !last_of_month {
+                ExpiryKind::Weekly
+            } else {
+                if let Some(previous) = previous_month {
+                    serial &= next_month(previous) == month;
+                }
+                previous_month = Some(month);
+                if serial {
+                    ExpiryKind::Monthly
+                } else {
+                    ExpiryKind::Quarterly
+                }
+            };
+            classified.push((date, kind));
+        }
+
+        Self {
+            name: name.to_string(),
+            expiries: classified,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// All expiries, earliest first.
+    pub fn expiries(&self) -> &[(NaiveDate, ExpiryKind)] {
+        &self.expiries
+    }
+
+    pub fn kind(&self, expiry: NaiveDate) -> Option<ExpiryKind> {
+        self.expiries.iter().find(|(date, _)| *date == expiry).map(|(_, kind)| *kind)
+    }
+
+    /// Expiries on or after `date`, earliest first.
+    pub fn upcoming(&self, date: NaiveDate) -> impl Iterator<Item = (NaiveDate, ExpiryKind)> + '_ {
+        self.expiries.iter().copied().filter(move |(expiry, _)| *expiry >= date)
+    }
+
+    /// Nearest expiry of any kind on or after `date`, usually the current weekly.
+    pub fn nearest(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.upcoming(date).next().map(|(expiry, _)| expiry)
+    }
+
+    /// Current month (near) monthly expiry on or after `date`.
+    pub fn current(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.monthly(date).next()
+    }
+
+    /// Next month monthly expiry.
+    pub fn next(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.monthly(date).nth(1)
+    }
+
+    /// Far month monthly expiry, the third one on or after `date`.
+    pub fn far(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.monthly(date).nth(2)
+    }
+
+    /// Trading days from `date` to `expiry`, counting the expiry day itself. `calendar` is the
+    /// contract's exchange calendar; special sessions such as Muhurat trading count as days.
+    pub fn trading_dte(date: NaiveDate, expiry: NaiveDate, calendar: &TradingCalendar) -> u32 {
+        calendar.trading_days_between(date, expiry)
+    }
+
+    fn monthly(&self, date: NaiveDate) -> impl Iterator<Item = NaiveDate> + '_ {
+        self.upcoming(date)
+            .filter(|(_, kind)| *kind != ExpiryKind::Weekly)
+            .map(|(expiry, _)| expiry)
+    }
+}
+
+fn next_month((year, month): (i32, u32)) -> (i32, u32) {
+    if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    }
+}
@@ -1,3 +1,5 @@
+use crate::data_models::clickhouse_enum;
+
 
 #[derive(Debug)]
 #[derive(serde_repr::Serialize_repr)]
@@ -9,4 +11,10 @@ pub enum FutureType {
     Atm = 1,
     AddToBase = 2,
     Liquid = 3,
-}
\ No newline at end of file
+}
+
+clickhouse_enum!(FutureType {
+    Atm => "atm",
+    AddToBase => "add_to_base",
+    Liquid => "liquid",
+});
@@ -1,21 +1,46 @@
 use crate::data_models::instrument_data::BaseExchange;
-use crate::data_models::futures_data::FutureType;
+use crate::data_models::calendar_data::TradingCalendar;
+use crate::data_models::futures_data::{ExpiryCalendar, FutureType};
+use crate::data_models::clickhouse_table;
+use crate::data_models::strike_ladder::StrikeLadder;
 use chrono::NaiveDate;
 
-#[derive(Debug, clickhouse::Row)]
-#[derive(serde::Serialize)]
-#[derive(serde::Deserialize)]
-#[derive(Clone)]
-#[derive(PartialEq)]
-pub struct FuturesData {
-    pub base_exchange: BaseExchange,
-    pub name: String,
-    #[serde(with = "clickhouse::serde::chrono::date")]
-    pub expiry: NaiveDate,
-    pub dte: u16,
-    pub future_type: FutureType,
-    pub underlying: u64,
-    pub base_expiry: u16,
-    pub add_to_base: u8,
-    pub strike: Vec<f64>,
+clickhouse_table! {
+    #[clickhouse_table(name = "futures", engine = "MergeTree()", order_by = "(base_exchange, name, expiry)")]
+    #[derive(Debug, clickhouse::Row)]
+    #[derive(serde::Serialize)]
+    #[derive(serde::Deserialize)]
+    #[derive(Clone)]
+    #[derive(PartialEq)]
+    pub struct FuturesData {
+        pub base_exchange: BaseExchange,
+        pub name: String,
+        #[serde(with = "clickhouse::serde::chrono::date")]
+        pub expiry: NaiveDate,
+        pub dte: u16,
+        pub future_type: FutureType,
+        pub underlying: u64,
+        /// The previous expiry of the same underlying, which this contract is priced against
+        /// when it is `AddToBase`. `None` for the nearest expiry.
+        #[serde(with = "clickhouse::serde::chrono::date::option")]
+        pub base_expiry: Option<NaiveDate>,
+        /// Price offset over the `base_expiry` contract. Maintained by hand and kept across
+        /// rebuilds of `futures_staging`.
+        pub add_to_base: f64,
+        pub strike: Vec<f64>,
+    }
+}
+
+impl FuturesData {
+    /// Replaces the calendar-day `dte` from `populate_future_staging` with trading days as of `date`,
+    /// counted on `calendar`, the calendar of this contract's `base_exchange`.
+    pub fn set_trading_dte(&mut self, date: NaiveDate, calendar: &TradingCalendar) {
+        let dte = ExpiryCalendar::trading_dte(date, self.expiry, calendar);
+        self.dte = dte.min(u16::MAX as u32) as u16;
+    }
+
+    /// The option strikes listed for this expiry.
+    pub fn strike_ladder(&self) -> StrikeLadder {
+        StrikeLadder::new(self.strike.clone())
+    }
 }
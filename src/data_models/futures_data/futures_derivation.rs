@@ -0,0 +1,109 @@
+use crate::data_models::futures_data::{FutureType, FuturesData};
+use crate::data_models::instrument_data::{BaseExchange, Instrument, InstrumentType};
+use chrono::NaiveDate;
+use std::collections::HashMap;
+
+/// Futures whose expiry is more than this many calendar days away, with no `Fut` listed, are `AddToBase`.
+pub const ADD_TO_BASE_MIN_DTE: u16 = 30;
+
+impl FutureType {
+    /// `Liquid` if a `Fut` contract is listed for the expiry, else `AddToBase` beyond
+    /// `ADD_TO_BASE_MIN_DTE`, else `Atm`.
+    pub fn classify(has_future: bool, dte: u16) -> FutureType {
+        if has_future {
+            FutureType::Liquid
+        } else if dte > ADD_TO_BASE_MIN_DTE {
+            FutureType::AddToBase
+        } else {
+            FutureType::Atm
+        }
+    }
+}
+
+#[derive(Default)]
+struct ExpiryGroup {
+    has_future: bool,
+    strikes: Vec<f64>,
+}
+
+impl FuturesData {
+    /// The rows `populate_future_staging` writes, derived from `instruments` without ClickHouse.
+    ///
+    /// Derivatives are grouped by `(base_exchange, name, expiry)`. `underlying` is the token of
+    /// the first `Eq` instrument with the same base exchange and name, or 0. `base_expiry` is the
+    /// previous expiry of the same underlying, or `None` for the nearest. `add_to_base` is kept
+    /// from the matching row of `existing`, or 0. Rows are ordered like the table: by base
+    /// exchange, name, then expiry.
+    ///
+    /// Instruments without an expiry are skipped, and so are instruments without a name. The SQL
+    /// differs there: it groups NULL names into rows of their own, which are not real contracts.
+    pub fn from_instruments(instruments: &[Instrument], existing: &[FuturesData], today: NaiveDate) -> Vec<FuturesData> {
+        let mut underlyings: HashMap<(BaseExchange, &str), u64> = HashMap::new();
+        let mut groups: HashMap<(BaseExchange, &str, NaiveDate), ExpiryGroup> = HashMap::new();
+        // `add_to_base` of each `existing` row, by the columns the SQL joins on.
+        let mut kept: HashMap<(BaseExchange, &str, NaiveDate, Option<NaiveDate>), f64> = HashMap::new();
+        for f in existing {
+            kept.entry((f.base_exchange, f.name.as_str(), f.expiry, f.base_expiry)).or_insert(f.add_to_base);
+        }
+
+        for instrument in instruments {
+            let Some(name) = instrument.name.as_deref() else {
+                continue;
+            };
+            if instrument.instrument_type == InstrumentType::Eq {
+                underlyings
+                    .entry((instrument.base_exchange, name))
+                    .or_insert_with(|| instrument.instrument_token.parse().unwrap_or(0));
+                continue;
+            }
+            let Some(expiry) = instrument.expiry else {
+                continue;
+            };
+            let group = groups.entry((instrument.base_exchange, name, expiry)).or_default();
+            group.has_future |= instrument.instrument_type == InstrumentType::Fut;
+            if instrument.strike != 0.0 && !group.strikes.contains(&instrument.strike) {
+                group.strikes.push(instrument.strike);
+            }
+        }
+
+        let mut keys: Vec<(BaseExchange, &str, NaiveDate)> = groups.keys().copied().collect();
+        keys.sort_by(|a, b| (a.0 as i8, a.1, a.2).cmp(&(b.0 as i8, b.1, b.2)));
+
+        let mut futures = Vec::with_capacity(keys.len());
+        let mut previous: Option<(BaseExchange, &str, NaiveDate)> = None;
+        for key in keys {
+            let (base_exchange, name, expiry) = key;
+            let group = groups.remove(&key).unwrap_or_default();
+
+            let base_expiry = match previous {
+                Some((prev_exchange, prev_name, prev_expiry)) if prev_exchange == base_exchange && prev_name == name => {
+                    Some(prev_expiry)
+                }
+                _ => None,
+            };
+            previous = Some(key);
+
+            let dte = (expiry - today).num_days().unsigned_abs().min(u16::MAX as u64) as u16;
+            let add_to_base = kept
+                .get(&(base_exchange, name, expiry, base_expiry))
+                .copied()
+                .unwrap_or(0.0);
+
+            let mut strike = group.strikes;
+            strike.sort_by(f64::total_cmp);
+
+            futures.push(FuturesData {
+                base_exchange,
+                name: name.to_string(),
+                expiry,
+                dte,
+                future_type: FutureType::classify(group.has_future, dte),
+                underlying: underlyings.get(&(base_exchange, name)).copied().unwrap_or(0),
+                base_expiry,
+                add_to_base,
+                strike,
+            });
+        }
+        futures
+    }
+}
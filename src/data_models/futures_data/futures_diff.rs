@@ -0,0 +1,116 @@
+use crate::data_models::futures_data::FuturesData;
+use crate::data_models::instrument_data::BaseExchange;
+use chrono::NaiveDate;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Columns compared by `FuturesDiff`. The key columns are not listed, and `dte` is left out
+/// because it changes every day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FuturesField {
+    FutureType,
+    Underlying,
+    BaseExpiry,
+    AddToBase,
+    Strike,
+}
+
+impl FuturesField {
+    /// Column name in the `futures` table.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FuturesField::FutureType => "future_type",
+            FuturesField::Underlying => "underlying",
+            FuturesField::BaseExpiry => "base_expiry",
+            FuturesField::AddToBase => "add_to_base",
+            FuturesField::Strike => "strike",
+        }
+    }
+}
+
+impl fmt::Display for FuturesField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A future present in both tables whose columns differ.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuturesChange {
+    pub old: FuturesData,
+    pub new: FuturesData,
+    pub changed: Vec<FuturesField>,
+}
+
+/// Differences between two sets of `futures` rows, keyed by `(base_exchange, name, expiry)`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FuturesDiff {
+    /// In `new` only, in its order.
+    pub added: Vec<FuturesData>,
+    /// In `old` only, e.g. expired contracts, in its order.
+    pub removed: Vec<FuturesData>,
+    pub modified: Vec<FuturesChange>,
+}
+
+impl FuturesDiff {
+    pub fn between(old: &[FuturesData], new: &[FuturesData]) -> Self {
+        let mut diff = FuturesDiff::default();
+        let mut old_by_key = HashMap::with_capacity(old.len());
+        for future in old {
+            old_by_key.entry(key(future)).or_insert(future);
+        }
+        let new_keys: HashSet<_> = new.iter().map(key).collect();
+
+        for future in new {
+            match old_by_key.get(&key(future)).copied() {
+                None => diff.added.push(future.clone()),
+                Some(previous) => {
+                    let changed = changed_fields(previous, future);
+                    if !changed.is_empty() {
+                        diff.modified.push(FuturesChange {
+                            old: previous.clone(),
+                            new: future.clone(),
+                            changed,
+                        });
+                    }
+                }
+            }
+        }
+
+        diff.removed = old
+            .iter()
+            .filter(|f| !new_keys.contains(&key(f)))
+            .cloned()
+            .collect();
+
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+fn key(future: &FuturesData) -> (BaseExchange, &str, NaiveDate) {
+    (future.base_exchange, &future.name, future.expiry)
+}
+
+fn changed_fields(old: &FuturesData, new: &FuturesData) -> Vec<FuturesField> {
+    let mut changed = Vec::new();
+    if old.future_type != new.future_type {
+        changed.push(FuturesField::FutureType);
+    }
+    if old.underlying != new.underlying {
+        changed.push(FuturesField::Underlying);
+    }
+    if old.base_expiry != new.base_expiry {
+        changed.push(FuturesField::BaseExpiry);
+    }
+    if old.add_to_base != new.add_to_base {
+        changed.push(FuturesField::AddToBase);
+    }
+    if old.strike != new.strike {
+        changed.push(FuturesField::Strike);
+    }
+    changed
+}
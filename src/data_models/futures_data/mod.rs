@@ -1,6 +1,16 @@
 pub mod queries;
 mod future_type;
 mod futures;
+mod futures_derivation;
+mod futures_diff;
+mod continuous_futures;
+mod staging_validation;
+mod expiry_calendar;
 
 pub use future_type::FutureType;
 pub use futures::FuturesData;
+pub use futures_derivation::ADD_TO_BASE_MIN_DTE;
+pub use continuous_futures::{Adjustment, ContinuousCandle, ContinuousFutures, ContinuousSeries, Roll, RollRule};
+pub use futures_diff::{FuturesChange, FuturesDiff, FuturesField};
+pub use staging_validation::{PromotionRules, StagingIssue};
+pub use expiry_calendar::{ExpiryCalendar, ExpiryKind};
@@ -1,5 +1,7 @@
-pub static POPULATE_FUTURE_STAGING:&str = r#"
-INSERT INTO futures_staging
+use crate::data_models::SchemaTarget;
+
+static POPULATE_FUTURE_STAGING: &str = r#"
+INSERT INTO ${futures_staging}
 SELECT
     f.base_exchange,
     f.name,
@@ -22,16 +24,16 @@ FROM (
             'atm'
         ) AS future_type,
         toUInt64OrZero(any(eq.instrument_token)) AS underlying,
-        lagInFrame(enriched.expiry, 1) OVER (
+        lagInFrame(toNullable(enriched.expiry), 1) OVER (
             PARTITION BY enriched.base_exchange, enriched.name
             ORDER BY enriched.expiry ASC
             ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW
         ) AS base_expiry,
         arraySort(arrayFilter(x -> x != 0 AND x IS NOT NULL, groupArrayDistinct(toFloat64(enriched.strike)))) AS strike
-    FROM instruments AS enriched
+    FROM ${instruments} AS enriched
     LEFT JOIN (
         SELECT base_exchange, name, instrument_token
-        FROM instruments
+        FROM ${instruments}
         WHERE instrument_type = 'Eq'
     ) AS eq
     ON enriched.base_exchange = eq.base_exchange AND enriched.name = eq.name
@@ -41,9 +43,15 @@ FROM (
         enriched.name,
         enriched.expiry
 ) AS f
-LEFT JOIN futures AS existing
+LEFT JOIN ${futures} AS existing
 ON f.base_exchange = existing.base_exchange
    AND f.name = existing.name
    AND f.expiry = existing.expiry
-   AND f.base_expiry = existing.base_expiry
+   AND isNotDistinctFrom(f.base_expiry, existing.base_expiry)
 "#;
+
+/// Rebuilds `futures_staging` from `instruments`, keeping `add_to_base` from `futures`.
+/// `FuturesData::from_instruments` derives the same rows in Rust.
+pub fn populate_future_staging(target: &SchemaTarget) -> String {
+    target.render(POPULATE_FUTURE_STAGING)
+}
@@ -1,32 +1,10 @@
+use crate::data_models::futures_data::FuturesData;
+use crate::data_models::{ClickHouseTable, SchemaTarget};
 
-pub static CREATE_FUTURES_STAGING_TABLE:&str = r#"
-CREATE TABLE IF NOT EXISTS futures_staging
-(
-    base_exchange Enum8('Bse' = 0, 'Mcx' = 1, 'Nse' = 2, 'Nseix' = 3, 'Global' = 4),
-    name String,
-    expiry Date,
-    dte UInt16,
-    future_type Enum8('atm' = 1, 'add_to_base' = 2, 'liquid' = 3),
-    underlying UInt64,
-    base_expiry Date,
-    add_to_base Float64,
-    strike Array(Float64)
-) ENGINE = MergeTree()
-ORDER BY (base_exchange, name, expiry);
-"#;
+pub fn create_futures_staging_table(target: &SchemaTarget) -> String {
+    FuturesData::create_table_named(&target.table("futures_staging"))
+}
 
-pub static CREATE_FUTURES_TABLE:&str = r#"
-CREATE TABLE IF NOT EXISTS futures
-(
-    base_exchange Enum8('Bse' = 0, 'Mcx' = 1, 'Nse' = 2, 'Nseix' = 3, 'Global' = 4),
-    name String,
-    expiry Date,
-    dte UInt16,
-    future_type Enum8('atm' = 1, 'add_to_base' = 2, 'liquid' = 3),
-    underlying UInt64,
-    base_expiry Date,
-    add_to_base Float64,
-    strike Array(Float64)
-) ENGINE = MergeTree()
-ORDER BY (base_exchange, name, expiry);
-"#;
+pub fn create_futures_table(target: &SchemaTarget) -> String {
+    FuturesData::create_table_in(target)
+}
@@ -0,0 +1,91 @@
+use crate::data_models::futures_data::{FutureType, FuturesData};
+use crate::data_models::instrument_data::BaseExchange;
+use chrono::NaiveDate;
+use std::fmt;
+
+/// Checks `futures_staging` must pass before it replaces `futures`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromotionRules {
+    /// Fewest rows staging may hold.
+    pub min_rows: usize,
+    /// Staging must hold at least this fraction of the live row count, e.g. 0.5 rejects a
+    /// staging table that lost half the futures to a partial instrument dump.
+    pub min_row_ratio: f64,
+    /// Reject `Atm` and `AddToBase` rows with an empty `strike` array. Those are priced off their
+    /// options; a `Liquid` future trades on its own and may have none listed.
+    pub require_strikes: bool,
+}
+
+impl Default for PromotionRules {
+    fn default() -> Self {
+        Self {
+            min_rows: 1,
+            min_row_ratio: 0.5,
+            require_strikes: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StagingIssue {
+    TooFewRows { staging: usize, min_rows: usize },
+    /// Staging shrank below `min_row_ratio` of the live table.
+    ShrankTooMuch { staging: usize, live: usize },
+    MissingStrikes {
+        base_exchange: BaseExchange,
+        name: String,
+        expiry: NaiveDate,
+    },
+}
+
+impl fmt::Display for StagingIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StagingIssue::TooFewRows { staging, min_rows } => {
+                write!(f, "staging has {} rows, expected at least {}", staging, min_rows)
+            }
+            StagingIssue::ShrankTooMuch { staging, live } => {
+                write!(f, "staging has {} rows against {} live", staging, live)
+            }
+            StagingIssue::MissingStrikes {
+                base_exchange,
+                name,
+                expiry,
+            } => write!(f, "{} {} {} has no strikes", base_exchange, name, expiry),
+        }
+    }
+}
+
+impl PromotionRules {
+    /// Everything wrong with `staging` as a replacement for `live`; empty if it may be promoted.
+    pub fn validate(&self, staging: &[FuturesData], live: &[FuturesData]) -> Vec<StagingIssue> {
+        let mut issues = Vec::new();
+
+        if staging.len() < self.min_rows {
+            issues.push(StagingIssue::TooFewRows {
+                staging: staging.len(),
+                min_rows: self.min_rows,
+            });
+        }
+        if (staging.len() as f64) < live.len() as f64 * self.min_row_ratio {
+            issues.push(StagingIssue::ShrankTooMuch {
+                staging: staging.len(),
+                live: live.len(),
+            });
+        }
+        if self.require_strikes {
+            issues.extend(
+                staging
+                    .iter()
+                    .filter(|f| f.future_type != FutureType::Liquid && f.strike.is_empty())
+                    .map(|f| StagingIssue::MissingStrikes {
+                        base_exchange: f.base_exchange,
+                        name: f.name.clone(),
+                        expiry: f.expiry,
+                    }),
+            );
+        }
+
+        issues
+    }
+}
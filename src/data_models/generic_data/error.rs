@@ -0,0 +1,43 @@
+use std::fmt;
+
+/// **Kite Connect Session Errors**
+#[derive(Debug)]
+pub enum SessionError {
+    Http(String),
+    Api { error_type: String, message: String },
+    InvalidResponse(String),
+    ReloginRequired(String),
+    Store(String),
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SessionError::Http(msg) => write!(f, "Kite HTTP error: {}", msg),
+            SessionError::Api { error_type, message } => write!(f, "Kite API error ({}): {}", error_type, message),
+            SessionError::InvalidResponse(msg) => write!(f, "Invalid Kite response: {}", msg),
+            SessionError::ReloginRequired(msg) => write!(f, "Kite re-login required: {}", msg),
+            SessionError::Store(msg) => write!(f, "Session store error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+/// **User Parsing Errors**
+#[derive(Debug)]
+pub enum UserError {
+    Json { path: String, message: String },
+    InvalidField { field: String, message: String },
+}
+
+impl fmt::Display for UserError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UserError::Json { path, message } => write!(f, "Invalid user at `{}`: {}", path, message),
+            UserError::InvalidField { field, message } => write!(f, "Invalid user field `{}`: {}", field, message),
+        }
+    }
+}
+
+impl std::error::Error for UserError {}
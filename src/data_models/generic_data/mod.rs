@@ -1,3 +1,13 @@
 mod user;
+mod product;
+mod order_type;
+mod error;
+mod session;
+mod session_store;
 
-pub use user::User;
+pub use user::{User, UserMeta, IST, LOGIN_TIME_FORMAT, TOKEN_EXPIRY_TIME};
+pub use product::Product;
+pub use order_type::OrderType;
+pub use error::{SessionError, UserError};
+pub use session::{KiteSession, TokenRequest, KITE_API_URL, KITE_API_VERSION, KITE_LOGIN_URL};
+pub use session_store::{session_key, InMemorySessionStore, RedisSessionStore, SessionStore, SESSION_KEY_PREFIX};
@@ -0,0 +1,18 @@
+use crate::data_models::kite_enum;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OrderType {
+    Market,
+    Limit,
+    /// Stop-loss limit.
+    Sl,
+    /// Stop-loss market.
+    SlM,
+}
+
+kite_enum!(OrderType, "order type", {
+    Market => "MARKET",
+    Limit => "LIMIT",
+    Sl => "SL",
+    SlM => "SL-M",
+});
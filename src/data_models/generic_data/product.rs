@@ -0,0 +1,21 @@
+use crate::data_models::kite_enum;
+
+/// Kite margin product.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Product {
+    /// Cash and carry (delivery).
+    Cnc,
+    /// Margin intraday square-off.
+    Mis,
+    /// Normal F&O / commodity carry-forward.
+    Nrml,
+    /// Margin trading facility.
+    Mtf,
+}
+
+kite_enum!(Product, "product", {
+    Cnc => "CNC",
+    Mis => "MIS",
+    Nrml => "NRML",
+    Mtf => "MTF",
+});
@@ -0,0 +1,135 @@
+use crate::config_models::ZerodhaConfig;
+use crate::data_models::generic_data::{SessionError, User};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+pub const KITE_LOGIN_URL: &str = "https://kite.zerodha.com/connect/login";
+pub const KITE_API_URL: &str = "https://api.kite.trade";
+pub const KITE_API_VERSION: &str = "3";
+
+/// The form request that exchanges a `request_token` for an access token.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenRequest {
+    pub url: String,
+    pub params: Vec<(String, String)>,
+}
+
+/// Kite Connect login flow for the credentials in a `ZerodhaConfig`.
+#[derive(Debug, Clone)]
+pub struct KiteSession {
+    account_id: String,
+    api_key: String,
+    api_secret: String,
+    login_url: String,
+    api_url: String,
+    http: reqwest::Client,
+}
+
+impl KiteSession {
+    /// Creates a session against the public Kite endpoints.
+    pub fn new(config: &ZerodhaConfig) -> Self {
+        Self {
+            account_id: config.account_id.clone(),
+            api_key: config.api_key.clone(),
+            api_secret: config.api_secret.clone(),
+            login_url: KITE_LOGIN_URL.to_string(),
+            api_url: KITE_API_URL.to_string(),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// Overrides the API base URL (e.g. a local mock server in tests).
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.api_url = api_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Overrides the login page base URL.
+    pub fn with_login_url(mut self, login_url: impl Into<String>) -> Self {
+        self.login_url = login_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Id of the configured account this session logs in to.
+    pub fn account_id(&self) -> &str {
+        &self.account_id
+    }
+
+    pub fn api_key(&self) -> &str {
+        &self.api_key
+    }
+
+    /// URL the user opens in a browser to obtain a `request_token`.
+    pub fn login_url(&self) -> String {
+        format!("{}?v={}&api_key={}", self.login_url, KITE_API_VERSION, self.api_key)
+    }
+
+    /// SHA-256 hex digest of `api_key + request_token + api_secret`.
+    pub fn checksum(&self, request_token: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.api_key.as_bytes());
+        hasher.update(request_token.as_bytes());
+        hasher.update(self.api_secret.as_bytes());
+        hex::encode(hasher.finalize())
+    }
+
+    /// Builds the `POST /session/token` request for a `request_token`.
+    pub fn token_request(&self, request_token: &str) -> TokenRequest {
+        TokenRequest {
+            url: format!("{}/session/token", self.api_url),
+            params: vec![
+                ("api_key".to_string(), self.api_key.clone()),
+                ("request_token".to_string(), request_token.to_string()),
+                ("checksum".to_string(), self.checksum(request_token)),
+            ],
+        }
+    }
+
+    /// Exchanges a `request_token` for a logged-in `User` session.
+    pub async fn generate_session(&self, request_token: &str) -> Result<User, SessionError> {
+        let request = self.token_request(request_token);
+
+        let response = self
+            .http
+            .post(&request.url)
+            .header("X-Kite-Version", KITE_API_VERSION)
+            .form(&request.params)
+            .send()
+            .await
+            .map_err(|e| SessionError::Http(e.to_string()))?;
+
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| SessionError::Http(e.to_string()))?;
+
+        Self::parse_session_response(&body).map_err(|e| match e {
+            SessionError::InvalidResponse(msg) if !status.is_success() => {
+                SessionError::Http(format!("{}: {}", status, msg))
+            }
+            e => e,
+        })
+    }
+
+    /// Parses the `{"status": ..., "data": ...}` envelope returned by Kite.
+    pub fn parse_session_response(body: &str) -> Result<User, SessionError> {
+        let json: Value = serde_json::from_str(body)
+            .map_err(|e| SessionError::InvalidResponse(format!("Body is not JSON: {}", e)))?;
+
+        match json.get("status").and_then(|v| v.as_str()) {
+            Some("success") => {
+                let data = json
+                    .get("data")
+                    .cloned()
+                    .ok_or_else(|| SessionError::InvalidResponse("Missing `data`".to_string()))?;
+                User::from_json(data).map_err(|e| SessionError::InvalidResponse(e.to_string()))
+            }
+            Some("error") => Err(SessionError::Api {
+                error_type: json["error_type"].as_str().unwrap_or("UnknownException").to_string(),
+                message: json["message"].as_str().unwrap_or_default().to_string(),
+            }),
+            _ => Err(SessionError::InvalidResponse("Missing or invalid `status`".to_string())),
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use crate::config_models::{Config, RedisConfig, RedisConnType, RedisDBType};
+use crate::data_models::generic_data::{SessionError, User};
+use redis::Commands;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use time::OffsetDateTime;
+
+/// Prefix of the Redis keys holding Kite sessions in the API DB.
+pub const SESSION_KEY_PREFIX: &str = "kite:session";
+
+/// Redis key of the session for one configured account, e.g. `kite:session:research`.
+pub fn session_key(account_id: &str) -> String {
+    format!("{}:{}", SESSION_KEY_PREFIX, account_id)
+}
+
+/// Storage for the logged-in Kite `User` session.
+pub trait SessionStore {
+    fn load(&self) -> Result<Option<User>, SessionError>;
+
+    fn save(&self, user: &User) -> Result<(), SessionError>;
+
+    fn clear(&self) -> Result<(), SessionError>;
+
+    /// Returns the stored session if it is still valid at `now`.
+    fn current(&self, now: OffsetDateTime) -> Result<User, SessionError> {
+        match self.load()? {
+            Some(user) if !user.is_expired(now) => Ok(user),
+            Some(user) => Err(SessionError::ReloginRequired(format!(
+                "session for {} expired at {}",
+                user.user_id,
+                user.expires_at()
+            ))),
+            None => Err(SessionError::ReloginRequired("no stored session".to_string())),
+        }
+    }
+}
+
+/// Process-local session store, mainly for tests and single-process tools.
+#[derive(Debug, Default)]
+pub struct InMemorySessionStore {
+    session: Mutex<Option<User>>,
+}
+
+impl InMemorySessionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SessionStore for InMemorySessionStore {
+    fn load(&self) -> Result<Option<User>, SessionError> {
+        let session = self.session.lock().map_err(|e| SessionError::Store(e.to_string()))?;
+        Ok(session.clone())
+    }
+
+    fn save(&self, user: &User) -> Result<(), SessionError> {
+        let mut session = self.session.lock().map_err(|e| SessionError::Store(e.to_string()))?;
+        *session = Some(user.clone());
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<(), SessionError> {
+        let mut session = self.session.lock().map_err(|e| SessionError::Store(e.to_string()))?;
+        *session = None;
+        Ok(())
+    }
+}
+
+/// Session store backed by the Redis API DB, keyed per account id.
+#[derive(Debug, Clone)]
+pub struct RedisSessionStore {
+    client: redis::Client,
+    key: String,
+}
+
+impl RedisSessionStore {
+    /// Connects with the write user to the API DB from `Config`.
+    pub fn new(config: &Config, account_id: &str) -> Result<Self, SessionError> {
+        let redis_config = RedisConfig::new(config, RedisDBType::Api, RedisConnType::Write)
+            .map_err(|e| SessionError::Store(e.to_string()))?;
+        Self::from_redis_config(&redis_config, account_id)
+    }
+
+    pub fn from_redis_config(redis_config: &RedisConfig, account_id: &str) -> Result<Self, SessionError> {
+        let client = redis::Client::open(redis_config.connection_string())
+            .map_err(|e| SessionError::Store(e.to_string()))?;
+        Ok(Self {
+            client,
+            key: session_key(account_id),
+        })
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Overrides the Redis key the session is stored under.
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.key = key.into();
+        self
+    }
+
+    fn connection(&self) -> Result<redis::Connection, SessionError> {
+        self.client
+            .get_connection()
+            .map_err(|e| SessionError::Store(e.to_string()))
+    }
+}
+
+impl SessionStore for RedisSessionStore {
+    fn load(&self) -> Result<Option<User>, SessionError> {
+        let fields: HashMap<String, String> = self
+            .connection()?
+            .hgetall(&self.key)
+            .map_err(|e| SessionError::Store(e.to_string()))?;
+
+        if fields.is_empty() {
+            return Ok(None);
+        }
+        User::from_redis_fields(&fields)
+            .map(Some)
+            .map_err(|e| SessionError::Store(e.to_string()))
+    }
+
+    fn save(&self, user: &User) -> Result<(), SessionError> {
+        // Replace the whole hash so fields dropped from `user` (e.g. `avatar_url`) don't linger,
+        // and let Redis drop the session at the same cutoff Kite uses.
+        redis::pipe()
+            .atomic()
+            .del(&self.key)
+            .ignore()
+            .cmd("HSET")
+            .arg(&self.key)
+            .arg(user)
+            .ignore()
+            .cmd("EXPIREAT")
+            .arg(&self.key)
+            .arg(user.expires_at().unix_timestamp())
+            .ignore()
+            .query::<()>(&mut self.connection()?)
+            .map_err(|e| SessionError::Store(e.to_string()))
+    }
+
+    fn clear(&self) -> Result<(), SessionError> {
+        self.connection()?
+            .del::<_, ()>(&self.key)
+            .map_err(|e| SessionError::Store(e.to_string()))
+    }
+}
@@ -1,29 +1,60 @@
+use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
-use time::PrimitiveDateTime;
-use time::macros::format_description;
-use redis::ToRedisArgs;
+use time::{Duration, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+use time::format_description::BorrowedFormatItem;
+use time::macros::{format_description, offset, time};
+use redis::{ErrorKind, FromRedisValue, RedisResult, ToRedisArgs};
 use serde_json::Value;
+use crate::data_models::generic_data::{OrderType, Product, UserError};
+use crate::data_models::instrument_data::Exchange;
+use crate::data_models::kite_str::KiteName;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Kite reports `login_time` in Indian Standard Time.
+pub const IST: UtcOffset = offset!(+5:30);
+
+/// Kite invalidates access tokens every day at 06:00 IST.
+pub const TOKEN_EXPIRY_TIME: Time = time!(6:00);
+
+/// Format of `login_time` in Kite responses, e.g. `2021-01-01 16:15:14`.
+pub const LOGIN_TIME_FORMAT: &[BorrowedFormatItem<'static>] =
+    format_description!("[year]-[month]-[day] [hour]:[minute]:[second]");
+
+time::serde::format_description!(kite_login_time, PrimitiveDateTime, LOGIN_TIME_FORMAT);
+
+/// How `login_time` was stored in Redis before it used `LOGIN_TIME_FORMAT`: `PrimitiveDateTime`'s
+/// `Display`, e.g. `2024-01-01 9:00:14.0`. Still accepted when reading a session hash.
+const LEGACY_LOGIN_TIME_FORMAT: &[BorrowedFormatItem<'static>] =
+    format_description!("[year]-[month]-[day] [hour padding:none]:[minute]:[second].[subsecond]");
+
+/// Hash fields whose values are stored as JSON rather than plain strings.
+const JSON_FIELDS: [&str; 4] = ["exchanges", "products", "order_types", "meta"];
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct UserMeta {
+    #[serde(default)]
     pub demat_consent: String,
 }
 
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct User {
     pub access_token: String,
     pub api_key: String,
+    #[serde(default)]
     pub avatar_url: Option<String>,
     pub broker: String,
     pub email: String,
+    #[serde(default)]
     pub enctoken: String,
-    pub exchanges: Vec<String>,
+    pub exchanges: Vec<KiteName<Exchange>>,
+    #[serde(with = "kite_login_time")]
     pub login_time: PrimitiveDateTime,
+    #[serde(default)]
     pub meta: UserMeta,
-    pub order_types: Vec<String>,
-    pub products: Vec<String>,
+    pub order_types: Vec<KiteName<OrderType>>,
+    pub products: Vec<KiteName<Product>>,
     pub public_token: String,
+    #[serde(default)]
     pub refresh_token: String,
     pub user_id: String,
     pub user_name: String,
@@ -32,121 +63,133 @@ pub struct User {
 }
 
 impl User {
-    pub fn from_json(json: Value) -> Result<User, String> {
-        let avatar_url = json["avatar_url"].as_str().map(|s| s.to_string());
-        // let avatar_url = match avatar_url {
-        //     Some(avatar_url) => Some(avatar_url.to_string()),
-        //     None => None,
-        // };
-
-        let date_format = format_description!("[year]-[month]-[day] [hour]:[minute]:[second]");
-        let login_time = match json["login_time"].as_str() {
-            Some(login_time) => {
-                let login_time = PrimitiveDateTime::parse(login_time, &date_format);
-                match login_time {
-                    Ok(login_time) => login_time,
-                    Err(e) => {
-                        return Err(format!("Error parsing login_time: {}", e));
-                    }
-                }
-            }
-            None => {
-                return Err("login_time is missing".to_string());
-            }
-        };
-
-        Ok(User {
-            user_type: json["user_type"].as_str().unwrap().to_string(),
-            email: json["email"].as_str().unwrap().to_string(),
-            user_name: json["user_name"].as_str().unwrap().to_string(),
-            user_shortname: json["user_shortname"].as_str().unwrap().to_string(),
-            broker: json["broker"].as_str().unwrap().to_string(),
-            exchanges: serde_json::from_value(json["exchanges"].clone()).unwrap(),
-            products: serde_json::from_value(json["products"].clone()).unwrap(),
-            order_types: serde_json::from_value(json["order_types"].clone()).unwrap(),
-            avatar_url,
-            user_id: json["user_id"].as_str().unwrap().to_string(),
-            api_key: json["api_key"].as_str().unwrap().to_string(),
-            access_token: json["access_token"].as_str().unwrap().to_string(),
-            public_token: json["public_token"].as_str().unwrap().to_string(),
-            enctoken: json["enctoken"].as_str().unwrap().to_string(),
-            refresh_token: json["refresh_token"].as_str().unwrap().to_string(),
-            login_time,
-            meta: serde_json::from_value(json["meta"].clone()).unwrap(),
+    /// Parses the `data` object of a Kite session/profile response.
+    pub fn from_json(json: Value) -> Result<User, UserError> {
+        serde_path_to_error::deserialize(json).map_err(|e| UserError::Json {
+            path: e.path().to_string(),
+            message: e.inner().to_string(),
         })
     }
-}
-
-impl ToRedisArgs for User {
-    fn write_redis_args<W>(&self, out: &mut W)
-    where
-        W: ?Sized + redis::RedisWrite,
-    {
-        // Write each field as a separate hash field
-        out.write_arg("user_type".as_bytes());
-        out.write_arg(self.user_type.as_bytes());
-
-        out.write_arg("email".as_bytes());
-        out.write_arg(self.email.as_bytes());
 
-        out.write_arg("user_name".as_bytes());
-        out.write_arg(self.user_name.as_bytes());
-
-        out.write_arg("user_shortname".as_bytes());
-        out.write_arg(self.user_shortname.as_bytes());
-
-        out.write_arg("broker".as_bytes());
-        out.write_arg(self.broker.as_bytes());
-
-        out.write_arg("exchanges".as_bytes());
-        out.write_arg(
-            serde_json::to_string(&self.exchanges)
-                .unwrap()
-                .as_bytes(),
-        );
-
-        out.write_arg("products".as_bytes());
-        out.write_arg(
-            serde_json::to_string(&self.products)
-                .unwrap()
-                .as_bytes(),
-        );
+    /// Rebuilds a `User` from the hash fields written by `write_redis_args`.
+    pub fn from_redis_fields(fields: &HashMap<String, String>) -> Result<User, UserError> {
+        let mut json = serde_json::Map::new();
+        for (field, value) in fields {
+            let value = if JSON_FIELDS.contains(&field.as_str()) {
+                serde_json::from_str(value).map_err(|e| UserError::InvalidField {
+                    field: field.clone(),
+                    message: e.to_string(),
+                })?
+            } else if field == "login_time" {
+                Value::String(stored_login_time(value))
+            } else {
+                Value::String(value.clone())
+            };
+            json.insert(field.clone(), value);
+        }
+        User::from_json(Value::Object(json))
+    }
 
-        out.write_arg("order_types".as_bytes());
-        out.write_arg(
-            serde_json::to_string(&self.order_types)
-                .unwrap()
-                .as_bytes(),
-        );
+    /// Hash fields in the order they are written to Redis. `avatar_url` is omitted when unset.
+    fn redis_fields(&self) -> Vec<(&'static str, String)> {
+        let mut fields = vec![
+            ("user_type", self.user_type.clone()),
+            ("email", self.email.clone()),
+            ("user_name", self.user_name.clone()),
+            ("user_shortname", self.user_shortname.clone()),
+            ("broker", self.broker.clone()),
+            ("exchanges", kite_names(&self.exchanges)),
+            ("products", kite_names(&self.products)),
+            ("order_types", kite_names(&self.order_types)),
+        ];
+        if let Some(avatar_url) = &self.avatar_url {
+            fields.push(("avatar_url", avatar_url.clone()));
+        }
+        fields.extend([
+            ("user_id", self.user_id.clone()),
+            ("api_key", self.api_key.clone()),
+            ("access_token", self.access_token.clone()),
+            ("public_token", self.public_token.clone()),
+            ("enctoken", self.enctoken.clone()),
+            ("refresh_token", self.refresh_token.clone()),
+            ("login_time", self.login_time.format(LOGIN_TIME_FORMAT).unwrap()),
+            ("meta", serde_json::to_string(&self.meta).unwrap()),
+        ]);
+        fields
+    }
 
-        //aVATAR URL CAN BE NULL
+    pub fn has_exchange(&self, exchange: Exchange) -> bool {
+        self.exchanges.contains(&KiteName::Known(exchange))
+    }
 
-        // out.write_arg("avatar_url".as_bytes());
-        // out.write_arg(self.avatar_url.as_bytes());
+    pub fn has_product(&self, product: Product) -> bool {
+        self.products.contains(&KiteName::Known(product))
+    }
 
-        out.write_arg("user_id".as_bytes());
-        out.write_arg(self.user_id.as_bytes());
+    pub fn has_order_type(&self, order_type: OrderType) -> bool {
+        self.order_types.contains(&KiteName::Known(order_type))
+    }
 
-        out.write_arg("api_key".as_bytes());
-        out.write_arg(self.api_key.as_bytes());
+    /// Whether the user is enabled for `exchange` and allowed the `product`, e.g. NFO with MIS.
+    pub fn can_trade(&self, exchange: Exchange, product: Product) -> bool {
+        self.has_exchange(exchange) && self.has_product(product)
+    }
 
-        out.write_arg("access_token".as_bytes());
-        out.write_arg(self.access_token.as_bytes());
+    /// `login_time` as an IST timestamp.
+    pub fn login_time_ist(&self) -> OffsetDateTime {
+        self.login_time.assume_offset(IST)
+    }
 
-        out.write_arg("public_token".as_bytes());
-        out.write_arg(self.public_token.as_bytes());
+    /// The first 06:00 IST cutoff after `login_time`, when the access token stops working.
+    pub fn expires_at(&self) -> OffsetDateTime {
+        let login = self.login_time_ist();
+        let cutoff = login.replace_time(TOKEN_EXPIRY_TIME);
+        if login < cutoff {
+            cutoff
+        } else {
+            cutoff + Duration::days(1)
+        }
+    }
 
-        out.write_arg("enctoken".as_bytes());
-        out.write_arg(self.enctoken.as_bytes());
+    pub fn is_expired(&self, now: OffsetDateTime) -> bool {
+        now >= self.expires_at()
+    }
+}
 
-        out.write_arg("refresh_token".as_bytes());
-        out.write_arg(self.refresh_token.as_bytes());
+/// JSON array of Kite names, matching what the session response contains.
+fn kite_names<T: std::fmt::Display>(values: &[T]) -> String {
+    serde_json::to_string(&values.iter().map(|v| v.to_string()).collect::<Vec<_>>()).unwrap()
+}
 
-        out.write_arg("login_time".as_bytes());
-        out.write_arg(self.login_time.to_string().as_bytes());
+impl ToRedisArgs for User {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + redis::RedisWrite,
+    {
+        // Write each field as a separate hash field
+        for (key, value) in self.redis_fields() {
+            out.write_arg(key.as_bytes());
+            out.write_arg(value.as_bytes());
+        }
+    }
+}
 
-        out.write_arg("meta".as_bytes());
-        out.write_arg(serde_json::to_string(&self.meta).unwrap().as_bytes());
+/// `login_time` from a session hash in `LOGIN_TIME_FORMAT`, rewriting the legacy format.
+/// Anything else is passed through for `from_json` to reject.
+fn stored_login_time(value: &str) -> String {
+    if PrimitiveDateTime::parse(value, LOGIN_TIME_FORMAT).is_ok() {
+        return value.to_string();
     }
+    PrimitiveDateTime::parse(value, LEGACY_LOGIN_TIME_FORMAT)
+        .ok()
+        .and_then(|login_time| login_time.format(LOGIN_TIME_FORMAT).ok())
+        .unwrap_or_else(|| value.to_string())
 }
 
+impl FromRedisValue for User {
+    fn from_redis_value(v: &redis::Value) -> RedisResult<Self> {
+        let fields: HashMap<String, String> = HashMap::from_redis_value(v)?;
+        User::from_redis_fields(&fields)
+            .map_err(|e| (ErrorKind::TypeError, "Invalid User hash", e.to_string()).into())
+    }
+}
@@ -1,17 +1,43 @@
 use chrono::{DateTime, Utc};
 use crate::data_models::hist_data::Interval;
+use crate::data_models::clickhouse_table;
 
-#[derive(Debug, clickhouse::Row, serde::Serialize, serde::Deserialize)]
-pub struct HistData {
-    pub tradingsymbol: String,
-    pub open: f64,
-    pub high: f64,
-    pub low: f64,
-    pub close: f64,
-    pub volume: u64,
+clickhouse_table! {
+    #[clickhouse_table(
+        name = "historical_data",
+        engine = "ReplacingMergeTree",
+        partition_by = "(tradingsymbol, interval)",
+        order_by = "(tradingsymbol, interval, datetime)",
+    )]
+    #[derive(Debug, clickhouse::Row, serde::Serialize, serde::Deserialize)]
+    pub struct HistData {
+        pub tradingsymbol: String,
+        pub open: f64,
+        pub high: f64,
+        pub low: f64,
+        pub close: f64,
+        pub volume: u64,
 
-    #[serde(with = "clickhouse::serde::chrono::datetime")]
-    pub datetime: DateTime<Utc>, // field name fixed, type corrected
+        #[serde(with = "clickhouse::serde::chrono::datetime")]
+        pub datetime: DateTime<Utc>, // field name fixed, type corrected
 
-    pub interval: Interval,
+        pub interval: Interval,
+    }
+}
+
+clickhouse_table! {
+    #[clickhouse_table(
+        name = "historical_data_max_datetime",
+        engine = "ReplacingMergeTree",
+        order_by = "(instrument_token, interval)",
+    )]
+    /// Latest candle stored per instrument and interval, used to resume backfills.
+    #[derive(Debug, Clone, clickhouse::Row, serde::Serialize, serde::Deserialize)]
+    pub struct HistDataMaxDatetime {
+        pub tradingsymbol: String,
+        pub instrument_token: String,
+        pub interval: Interval,
+        #[serde(with = "clickhouse::serde::chrono::datetime")]
+        pub max_datetime: DateTime<Utc>,
+    }
 }
@@ -1,3 +1,5 @@
+use crate::data_models::clickhouse_enum;
+
 
 #[derive(Debug, serde_repr::Serialize_repr, serde_repr::Deserialize_repr, Clone)]
 #[repr(i8)]
@@ -40,3 +42,14 @@ impl Interval {
         }
     }
 }
+
+clickhouse_enum!(Interval {
+    Day,
+    Minute,
+    ThreeMinute,
+    FiveMinute,
+    TenMinute,
+    FifteenMinute,
+    ThirtyMinute,
+    SixtyMinute,
+});
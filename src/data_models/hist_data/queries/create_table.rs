@@ -1,45 +1,10 @@
-pub static CREATE_HIST_DATA_TABLE:&str = r#"
-CREATE TABLE IF NOT EXISTS historical_data
-(
-    tradingsymbol String,
-    open Float64,
-    high Float64,
-    low Float64,
-    close Float64,
-    volume UInt64,
-    datetime DateTime('Asia/Kolkata'),
-    interval Enum8(
-        'Day' = 0,
-        'Minute' = 1,
-        'ThreeMinute' = 2,
-        'FiveMinute' = 3,
-        'TenMinute' = 4,
-        'FifteenMinute' = 5,
-        'ThirtyMinute' = 6,
-        'SixtyMinute' = 7
-    )
-)
-ENGINE = ReplacingMergeTree
-PARTITION BY (tradingsymbol, interval)
-ORDER BY (tradingsymbol, interval, datetime)
-"#;
+use crate::data_models::hist_data::{HistData, HistDataMaxDatetime};
+use crate::data_models::{ClickHouseTable, SchemaTarget};
 
-pub static CREATE_MAX_DATETIME_HIST_DATA_TABLE:&str = r#"
-CREATE TABLE IF NOT EXISTS historical_data_max_datetime (
-        tradingsymbol String,
-        instrument_token String,
-        interval Enum8(
-            'Day' = 0,
-            'Minute' = 1,
-            'ThreeMinute' = 2,
-            'FiveMinute' = 3,
-            'TenMinute' = 4,
-            'FifteenMinute' = 5,
-            'ThirtyMinute' = 6,
-            'SixtyMinute' = 7
-        ),
-        max_datetime DateTime('Asia/Kolkata'),
-)
-ENGINE = ReplacingMergeTree
-ORDER BY (instrument_token,interval);
-"#;
+pub fn create_hist_data_table(target: &SchemaTarget) -> String {
+    HistData::create_table_in(target)
+}
+
+pub fn create_max_datetime_hist_data_table(target: &SchemaTarget) -> String {
+    HistDataMaxDatetime::create_table_in(target)
+}
@@ -1,10 +1,9 @@
+use crate::data_models::{clickhouse_enum, kite_enum};
 
 #[derive(Debug, serde_repr::Serialize_repr, serde_repr::Deserialize_repr)]
-
-#[derive(Clone)]
-#[derive(PartialEq)]
+#[derive(Clone, Copy)]
+#[derive(PartialEq, Eq, Hash)]
 #[repr(i8)]
-
 pub enum BaseExchange {
     Bse = 0,
     Mcx = 1,
@@ -13,4 +12,12 @@ pub enum BaseExchange {
     Global = 4,
 }
 
+kite_enum!(#[repr(i8)] BaseExchange, "base exchange", {
+    Bse => "BSE",
+    Mcx => "MCX",
+    Nse => "NSE",
+    Nseix => "NSEIX",
+    Global => "GLOBAL",
+});
 
+clickhouse_enum!(BaseExchange { Bse, Mcx, Nse, Nseix, Global });
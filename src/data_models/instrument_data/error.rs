@@ -0,0 +1,63 @@
+use crate::data_models::instrument_data::{BaseExchange, Exchange, Segment};
+use std::fmt;
+
+/// **Instrument Mapping Errors**
+#[derive(Debug, Clone, PartialEq)]
+pub enum InstrumentError {
+    Missing(&'static str),
+    Invalid { field: &'static str, value: String },
+    /// The segment is not one Kite lists under the exchange.
+    SegmentMismatch { exchange: Exchange, segment: Segment },
+    /// The base exchange does not match `Exchange::base_exchange`.
+    BaseExchangeMismatch { exchange: Exchange, base_exchange: BaseExchange },
+}
+
+impl fmt::Display for InstrumentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InstrumentError::Missing(field) => write!(f, "Missing `{}`", field),
+            InstrumentError::Invalid { field, value } => write!(f, "Invalid `{}` value: {}", field, value),
+            InstrumentError::SegmentMismatch { exchange, segment } => {
+                write!(f, "Segment {} does not belong to exchange {}", segment, exchange)
+            }
+            InstrumentError::BaseExchangeMismatch { exchange, base_exchange } => {
+                write!(f, "Base exchange {} does not match exchange {}", base_exchange, exchange)
+            }
+        }
+    }
+}
+
+impl std::error::Error for InstrumentError {}
+
+/// **Instrument CSV Errors**
+#[derive(Debug)]
+pub enum InstrumentCsvError {
+    /// The file could not be opened or its header read.
+    Io(String),
+    /// The row is not valid CSV (e.g. wrong number of columns).
+    Csv { line: u64, message: String },
+    /// The row is valid CSV but does not map to an `Instrument`.
+    Row { line: u64, error: InstrumentError },
+}
+
+impl InstrumentCsvError {
+    /// 1-based line of the offending row, if the error belongs to one.
+    pub fn line(&self) -> Option<u64> {
+        match self {
+            InstrumentCsvError::Io(_) => None,
+            InstrumentCsvError::Csv { line, .. } | InstrumentCsvError::Row { line, .. } => Some(*line),
+        }
+    }
+}
+
+impl fmt::Display for InstrumentCsvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InstrumentCsvError::Io(msg) => write!(f, "Instrument CSV I/O error: {}", msg),
+            InstrumentCsvError::Csv { line, message } => write!(f, "Malformed CSV at line {}: {}", line, message),
+            InstrumentCsvError::Row { line, error } => write!(f, "Invalid instrument at line {}: {}", line, error),
+        }
+    }
+}
+
+impl std::error::Error for InstrumentCsvError {}
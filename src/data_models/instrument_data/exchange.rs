@@ -1,6 +1,8 @@
-
+use crate::data_models::{clickhouse_enum, kite_enum};
+use crate::data_models::instrument_data::{BaseExchange, Segment};
 
 #[derive(Debug, serde_repr::Serialize_repr, serde_repr::Deserialize_repr)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(i8)]
 pub enum Exchange {
     Bcd = 0,
@@ -15,4 +17,55 @@ pub enum Exchange {
     Global = 9,
 }
 
+kite_enum!(#[repr(i8)] Exchange, "exchange", {
+    Bcd => "BCD",
+    Bfo => "BFO",
+    Bse => "BSE",
+    Cds => "CDS",
+    Mcx => "MCX",
+    Nco => "NCO",
+    Nfo => "NFO",
+    Nse => "NSE",
+    Nseix => "NSEIX",
+    Global => "GLOBAL",
+});
+
+clickhouse_enum!(Exchange { Bcd, Bfo, Bse, Cds, Mcx, Nco, Nfo, Nse, Nseix, Global });
+
+impl Exchange {
+    /// The exchange that lists the underlying, e.g. `NFO` contracts settle against `NSE`.
+    pub fn base_exchange(&self) -> BaseExchange {
+        match self {
+            Exchange::Bcd => BaseExchange::Bse,
+            Exchange::Bfo => BaseExchange::Bse,
+            Exchange::Bse => BaseExchange::Bse,
+            Exchange::Cds => BaseExchange::Nse,
+            Exchange::Mcx => BaseExchange::Mcx,
+            Exchange::Nco => BaseExchange::Nse,
+            Exchange::Nfo => BaseExchange::Nse,
+            Exchange::Nse => BaseExchange::Nse,
+            Exchange::Nseix => BaseExchange::Nseix,
+            Exchange::Global => BaseExchange::Global,
+        }
+    }
 
+    /// Segments Kite lists instruments under for this exchange.
+    pub fn segments(&self) -> &'static [Segment] {
+        match self {
+            Exchange::Bcd => &[Segment::BcdFut, Segment::BcdOpt],
+            Exchange::Bfo => &[Segment::BfoFut, Segment::BfoOpt],
+            Exchange::Bse => &[Segment::Bse, Segment::Indices],
+            Exchange::Cds => &[Segment::CdsFut, Segment::CdsOpt],
+            Exchange::Mcx => &[Segment::McxFut, Segment::McxOpt, Segment::Indices],
+            Exchange::Nco => &[Segment::Nco, Segment::NcoFut, Segment::NcoOpt],
+            Exchange::Nfo => &[Segment::NfoFut, Segment::NfoOpt],
+            Exchange::Nse => &[Segment::Nse, Segment::Indices],
+            Exchange::Nseix => &[Segment::Indices],
+            Exchange::Global => &[Segment::Indices],
+        }
+    }
+
+    pub fn has_segment(&self, segment: Segment) -> bool {
+        self.segments().contains(&segment)
+    }
+}
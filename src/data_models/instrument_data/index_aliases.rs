@@ -0,0 +1,129 @@
+use crate::config_models::{error::ConfigError, Config, IndexAliasConfig};
+use once_cell::sync::Lazy;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+/// Kite index names that differ from the symbol used across the rest of the platform.
+const BUILTIN_ALIASES: [(&str, &str); 6] = [
+    ("NIFTY 50", "NIFTY"),
+    ("NIFTY BANK", "BANKNIFTY"),
+    ("NIFTY MIDCAP SELECT (MIDCPNIFTY)", "MIDCPNIFTY"),
+    ("NIFTY NEXT 50", "NIFTYNXT50"),
+    ("NIFTY FIN SERVICE", "FINNIFTY"),
+    ("BSE INDEX BANKEX", "BANKEX"),
+];
+
+static BUILTIN: Lazy<IndexAliases> = Lazy::new(IndexAliases::default);
+
+/// Maps Kite index display names (e.g. `NIFTY BANK`) to canonical symbols (e.g. `BANKNIFTY`) and back.
+///
+/// `IndexAliases::default()` holds the built-in aliases; more can be added from
+/// `config.toml` or a TOML data file of `"DISPLAY NAME" = "SYMBOL"` pairs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexAliases {
+    symbols: HashMap<String, String>,
+    display_names: HashMap<String, String>,
+}
+
+impl Default for IndexAliases {
+    fn default() -> Self {
+        let mut aliases = Self::empty();
+        for (display_name, symbol) in BUILTIN_ALIASES {
+            aliases.insert(display_name, symbol);
+        }
+        aliases
+    }
+}
+
+impl IndexAliases {
+    /// A registry with no aliases; names pass through unchanged.
+    pub fn empty() -> Self {
+        Self {
+            symbols: HashMap::new(),
+            display_names: HashMap::new(),
+        }
+    }
+
+    /// The shared built-in registry used when no other is supplied.
+    pub fn builtin() -> &'static IndexAliases {
+        &BUILTIN
+    }
+
+    /// The aliases for the loaded `Config`: the built-in ones overlaid with its `[index_aliases]`
+    /// section, if any. Pass the result to `Instrument::from_json_with`,
+    /// `Instrument::from_source_with` or `InstrumentCsvReader::with_aliases`.
+    pub fn from_config(config: &Config) -> Result<Self, ConfigError> {
+        match &config.index_aliases {
+            Some(section) => Self::from_section(section),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Built-in aliases overlaid with the `file` and inline `aliases` of an `[index_aliases]` section.
+    pub fn from_section(config: &IndexAliasConfig) -> Result<Self, ConfigError> {
+        let mut aliases = Self::default();
+        if let Some(file) = &config.file {
+            aliases.load_file(file)?;
+        }
+        aliases.extend(config.aliases.iter());
+        Ok(aliases)
+    }
+
+    /// Adds the aliases in a TOML data file, replacing any with the same display name.
+    pub fn load_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), ConfigError> {
+        let content = fs::read_to_string(path.as_ref()).map_err(|e| {
+            ConfigError::FileError(format!("Failed to read index aliases {}: {}", path.as_ref().display(), e))
+        })?;
+        let entries: BTreeMap<String, String> = toml::from_str(&content)
+            .map_err(|e| ConfigError::ParseError(format!("Invalid index aliases file: {}", e)))?;
+        self.extend(entries.iter());
+        Ok(())
+    }
+
+    /// Adds an alias. A symbol with several display names maps back to the last one inserted.
+    pub fn insert(&mut self, display_name: impl Into<String>, symbol: impl Into<String>) {
+        let display_name = display_name.into();
+        let symbol = symbol.into();
+        if let Some(previous) = self.symbols.insert(display_name.clone(), symbol.clone()) {
+            if self.display_names.get(&previous) == Some(&display_name) {
+                self.display_names.remove(&previous);
+            }
+        }
+        self.display_names.insert(symbol, display_name);
+    }
+
+    pub fn extend<I, K, V>(&mut self, entries: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        for (display_name, symbol) in entries {
+            self.insert(display_name, symbol);
+        }
+    }
+
+    /// Canonical symbol for a Kite display name, e.g. `NIFTY BANK` → `BANKNIFTY`.
+    pub fn symbol(&self, display_name: &str) -> Option<&str> {
+        self.symbols.get(display_name).map(String::as_str)
+    }
+
+    /// Kite display name for a canonical symbol, e.g. `BANKNIFTY` → `NIFTY BANK`.
+    pub fn display_name(&self, symbol: &str) -> Option<&str> {
+        self.display_names.get(symbol).map(String::as_str)
+    }
+
+    /// The canonical symbol if `name` is an alias, otherwise `name` itself.
+    pub fn canonical<'a>(&'a self, name: &'a str) -> &'a str {
+        self.symbol(name).unwrap_or(name)
+    }
+
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+}
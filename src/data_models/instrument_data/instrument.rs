@@ -2,156 +2,173 @@ use crate::data_models::instrument_data::Exchange;
 use crate::data_models::instrument_data::InstrumentType;
 use crate::data_models::instrument_data::Segment;
 use crate::data_models::instrument_data::BaseExchange;
+use crate::data_models::instrument_data::InstrumentError;
+use crate::data_models::instrument_data::IndexAliases;
+use crate::data_models::clickhouse_table;
 use chrono::NaiveDate;
 use serde_json::Value;
-use std::error::Error;
-
-#[derive(Debug, clickhouse::Row, serde::Serialize, serde::Deserialize)]
-pub struct Instrument {
-    pub instrument_token: String,
-    pub exchange_token: String,
-    pub tradingsymbol: String,
-    pub name: Option<String>,
-    pub last_price: f64,
-    #[serde(with = "clickhouse::serde::chrono::date::option")]
-    pub expiry: Option<NaiveDate>,
-    pub strike: f64,
-    pub tick_size: f64,
-    pub lot_size: u32,
-    pub exchange: Exchange,
-    pub segment: Segment,
-    pub instrument_type: InstrumentType,
-    pub base_exchange: BaseExchange,
+use std::borrow::Cow;
+use std::str::FromStr;
+
+clickhouse_table! {
+    #[clickhouse_table(name = "instruments", engine = "MergeTree()", order_by = "(instrument_token)")]
+    #[derive(Debug, Clone, PartialEq, clickhouse::Row, serde::Serialize, serde::Deserialize)]
+    pub struct Instrument {
+        pub instrument_token: String,
+        pub exchange_token: String,
+        pub tradingsymbol: String,
+        pub name: Option<String>,
+        pub last_price: f64,
+        #[serde(with = "clickhouse::serde::chrono::date::option")]
+        pub expiry: Option<NaiveDate>,
+        pub strike: f64,
+        pub tick_size: f64,
+        pub lot_size: u32,
+        pub exchange: Exchange,
+        pub segment: Segment,
+        pub instrument_type: InstrumentType,
+        pub base_exchange: BaseExchange,
+    }
+}
+
+/// A record of raw Kite instrument fields, e.g. a JSON object or a row of the instruments CSV.
+pub trait InstrumentSource {
+    /// The field as text, or `None` if it is absent or empty.
+    fn text(&self, field: &str) -> Option<Cow<'_, str>>;
+
+    /// The field as a number. Numeric strings are accepted.
+    fn number(&self, field: &str) -> Option<Result<f64, String>> {
+        self.text(field)
+            .map(|s| s.trim().parse::<f64>().map_err(|_| s.into_owned()))
+    }
+}
+
+impl InstrumentSource for Value {
+    fn text(&self, field: &str) -> Option<Cow<'_, str>> {
+        match self.get(field)? {
+            Value::String(s) if !s.is_empty() => Some(Cow::Borrowed(s.as_str())),
+            Value::Number(n) => Some(Cow::Owned(n.to_string())),
+            _ => None,
+        }
+    }
+
+    fn number(&self, field: &str) -> Option<Result<f64, String>> {
+        match self.get(field)? {
+            Value::Number(n) => n.as_f64().map(Ok),
+            Value::String(s) if !s.is_empty() => Some(s.trim().parse::<f64>().map_err(|_| s.clone())),
+            _ => None,
+        }
+    }
 }
 
 impl Instrument {
-    /// Creates an `Instrument` instance from a JSON `Value`.
-    pub fn from_json(item: &Value) -> Result<Self, Box<dyn Error>> {
-        let format = "%Y-%m-%d";
-
-        Ok(Instrument {
-            exchange: match item.get("exchange").and_then(|v| v.as_str()) {
-                Some("NSE") => Exchange::Nse,
-                Some("BSE") => Exchange::Bse,
-                Some("MCX") => Exchange::Mcx,
-                Some("NSEIX") => Exchange::Nseix,
-                Some("BCD") => Exchange::Bcd,
-                Some("BFO") => Exchange::Bfo,
-                Some("CDS") => Exchange::Cds,
-                Some("NCO") => Exchange::Nco,
-                Some("NFO") => Exchange::Nfo,
-                Some("GLOBAL") => Exchange::Global,
-                _ => return Err("Invalid or missing `exchange` value".into()),
-            },
+    /// Creates an `Instrument` instance from a JSON `Value`, with the built-in index aliases.
+    pub fn from_json(item: &Value) -> Result<Self, InstrumentError> {
+        Self::from_source(item)
+    }
 
-            exchange_token: item
-                .get("exchange_token")
-                .and_then(|v| v.as_str())
-                .ok_or("Missing `exchange_token`")?
-                .to_string(),
-
-            expiry: item.get("expiry").and_then(|v| v.as_str()).and_then(|s| {
-                if !s.is_empty() {
-                    NaiveDate::parse_from_str(s, &format).ok()
-                } else {
-                    None
-                }
-            }),
-
-            instrument_token: item
-                .get("instrument_token")
-                .and_then(|v| v.as_str())
-                .ok_or("Missing `instrument_token`")?
-                .to_string(),
-
-            instrument_type: match item.get("instrument_type").and_then(|v| v.as_str()) {
-                Some("EQ") => InstrumentType::Eq,
-                Some("FUT") => InstrumentType::Fut,
-                Some("CE") => InstrumentType::Ce,
-                Some("PE") => InstrumentType::Pe,
-                _ => return Err("Invalid or missing `instrument_type` value".into()),
-            },
+    /// Like `from_json`, rewriting index names with the given aliases,
+    /// e.g. `IndexAliases::from_config(&config)`.
+    pub fn from_json_with(item: &Value, aliases: &IndexAliases) -> Result<Self, InstrumentError> {
+        Self::from_source_with(item, aliases)
+    }
+
+    /// Maps Kite instrument fields to an `Instrument`. Shared by the JSON and CSV readers.
+    pub fn from_source<S: InstrumentSource + ?Sized>(item: &S) -> Result<Self, InstrumentError> {
+        Self::from_source_with(item, IndexAliases::builtin())
+    }
+
+    /// Like `from_source`, rewriting index names with the given aliases.
+    pub fn from_source_with<S: InstrumentSource + ?Sized>(
+        item: &S,
+        aliases: &IndexAliases,
+    ) -> Result<Self, InstrumentError> {
+        let exchange: Exchange = parse_enum(item, "exchange")?;
+
+        let instrument = Instrument {
+            exchange,
 
-            last_price: item
-                .get("last_price")
-                .and_then(|v| v.as_f64())
-                .unwrap_or(0.0),
-            lot_size: item
-                .get("lot_size")
-                .and_then(|v| {
-                    if let Some(u) = v.as_u64() {
-                        Some(u as u32)
-                    } else if let Some(s) = v.as_str() {
-                        s.parse::<u32>().ok()
-                    } else {
-                        None
-                    }
-                })
-                .ok_or("Missing or invalid `lot_size`")?,
-
-            name: item.get("name").and_then(|v| v.as_str()).map(|s| match s {
-                "NIFTY 50" => "NIFTY".to_string(),
-                "NIFTY BANK" => "BANKNIFTY".to_string(),
-                "NIFTY MIDCAP SELECT (MIDCPNIFTY)" => "MIDCPNIFTY".to_string(),
-                "NIFTY NEXT 50" => "NIFTYNXT50".to_string(),
-                "NIFTY FIN SERVICE" => "FINNIFTY".to_string(),
-                "BSE INDEX BANKEX" => "BANKEX".to_string(),
-                _ => s.to_string(),
-            }),
-
-            segment: match item.get("segment").and_then(|v| v.as_str()) {
-                Some("BCD-FUT") => Segment::BcdFut,
-                Some("BCD-OPT") => Segment::BcdOpt,
-                Some("BFO-FUT") => Segment::BfoFut,
-                Some("BFO-OPT") => Segment::BfoOpt,
-                Some("BSE") => Segment::Bse,
-                Some("CDS-FUT") => Segment::CdsFut,
-                Some("CDS-OPT") => Segment::CdsOpt,
-                Some("INDICES") => Segment::Indices,
-                Some("MCX-FUT") => Segment::McxFut,
-                Some("MCX-OPT") => Segment::McxOpt,
-                Some("NCO") => Segment::Nco,
-                Some("NCO-FUT") => Segment::NcoFut,
-                Some("NCO-OPT") => Segment::NcoOpt,
-                Some("NFO-FUT") => Segment::NfoFut,
-                Some("NFO-OPT") => Segment::NfoOpt,
-                Some("NSE") => Segment::Nse,
-                _ => return Err("Invalid or missing `segment` value".into()),
+            exchange_token: required(item, "exchange_token")?,
+
+            expiry: match item.text("expiry") {
+                Some(s) => Some(NaiveDate::parse_from_str(&s, "%Y-%m-%d").map_err(|_| invalid("expiry", &s))?),
+                None => None,
             },
 
-            strike: item.get("strike").and_then(|v| {
-                if let Some(f) = v.as_f64() {
-                    Some(f)
-                } else if let Some(s) = v.as_str() {
-                    s.parse::<f64>().ok()
-                } else {
-                    None
-                }
-            }).unwrap_or(0.0),
-            tick_size: item
-                .get("tick_size")
-                .and_then(|v| v.as_f64())
-                .unwrap_or(0.01),
-
-            tradingsymbol: item
-                .get("tradingsymbol")
-                .and_then(|v| v.as_str())
-                .ok_or("Missing `tradingsymbol`")?
-                .to_string(),
-
-            base_exchange: match item.get("exchange").and_then(|v| v.as_str()) {
-                Some("NSE") => BaseExchange::Nse,
-                Some("BSE") => BaseExchange::Bse,
-                Some("MCX") => BaseExchange::Mcx,
-                Some("NSEIX") => BaseExchange::Nseix,
-                Some("BCD") => BaseExchange::Bse,
-                Some("BFO") => BaseExchange::Bse,
-                Some("CDS") => BaseExchange::Nse,
-                Some("NCO") => BaseExchange::Nse,
-                Some("NFO") => BaseExchange::Nse,
-                Some("GLOBAL") => BaseExchange::Global,
-                _ => return Err("Invalid or missing `exchange` value".into()),
+            instrument_token: required(item, "instrument_token")?,
+
+            instrument_type: parse_enum(item, "instrument_type")?,
+
+            last_price: number_or(item, "last_price", 0.0)?,
+
+            lot_size: match item.text("lot_size") {
+                Some(s) => s.trim().parse::<u32>().map_err(|_| invalid("lot_size", &s))?,
+                None => return Err(InstrumentError::Missing("lot_size")),
             },
-        })
+
+            name: item.text("name").map(|s| aliases.canonical(&s).to_string()),
+
+            segment: parse_enum(item, "segment")?,
+
+            strike: number_or(item, "strike", 0.0)?,
+
+            tick_size: number_or(item, "tick_size", 0.01)?,
+
+            tradingsymbol: required(item, "tradingsymbol")?,
+
+            base_exchange: exchange.base_exchange(),
+        };
+        instrument.validate()?;
+        Ok(instrument)
+    }
+
+    /// Checks that `segment` and `base_exchange` agree with `exchange`.
+    pub fn validate(&self) -> Result<(), InstrumentError> {
+        if !self.exchange.has_segment(self.segment) {
+            return Err(InstrumentError::SegmentMismatch {
+                exchange: self.exchange,
+                segment: self.segment,
+            });
+        }
+        if self.base_exchange != self.exchange.base_exchange() {
+            return Err(InstrumentError::BaseExchangeMismatch {
+                exchange: self.exchange,
+                base_exchange: self.base_exchange,
+            });
+        }
+        Ok(())
+    }
+}
+
+fn invalid(field: &'static str, value: &str) -> InstrumentError {
+    InstrumentError::Invalid {
+        field,
+        value: value.to_string(),
+    }
+}
+
+fn required<S: InstrumentSource + ?Sized>(item: &S, field: &'static str) -> Result<String, InstrumentError> {
+    item.text(field)
+        .map(Cow::into_owned)
+        .ok_or(InstrumentError::Missing(field))
+}
+
+/// Parses a required field from its Kite wire name, e.g. `NFO-OPT`.
+fn parse_enum<S, T>(item: &S, field: &'static str) -> Result<T, InstrumentError>
+where
+    S: InstrumentSource + ?Sized,
+    T: FromStr,
+{
+    match item.text(field) {
+        Some(s) => s.parse().map_err(|_| invalid(field, &s)),
+        None => Err(InstrumentError::Missing(field)),
+    }
+}
+
+fn number_or<S: InstrumentSource + ?Sized>(item: &S, field: &'static str, default: f64) -> Result<f64, InstrumentError> {
+    match item.number(field) {
+        Some(Ok(n)) => Ok(n),
+        Some(Err(value)) => Err(InstrumentError::Invalid { field, value }),
+        None => Ok(default),
     }
 }
@@ -0,0 +1,144 @@
+use crate::data_models::instrument_data::{IndexAliases, Instrument, InstrumentCsvError, InstrumentError, InstrumentSource};
+use csv::{StringRecord, StringRecordsIntoIter};
+use flate2::read::GzDecoder;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+
+/// First two bytes of every gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// A CSV row paired with the header's column indices, looked up by column name.
+struct CsvRow<'a> {
+    columns: &'a HashMap<String, usize>,
+    record: &'a StringRecord,
+}
+
+impl InstrumentSource for CsvRow<'_> {
+    fn text(&self, field: &str) -> Option<Cow<'_, str>> {
+        let index = *self.columns.get(field)?;
+        self.record
+            .get(index)
+            .filter(|value| !value.is_empty())
+            .map(Cow::Borrowed)
+    }
+}
+
+/// Streams `Instrument`s out of the Kite instruments CSV dump.
+///
+/// Rows that fail to parse are yielded as errors carrying their line number;
+/// iteration carries on with the next row.
+pub struct InstrumentCsvReader<R: Read> {
+    headers: StringRecord,
+    /// Index of each header; the first wins if a name repeats.
+    columns: HashMap<String, usize>,
+    records: StringRecordsIntoIter<R>,
+    aliases: IndexAliases,
+    finished: bool,
+}
+
+impl InstrumentCsvReader<Box<dyn Read>> {
+    /// Opens a plain or gzip-compressed dump file.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, InstrumentCsvError> {
+        let file = File::open(path.as_ref()).map_err(|e| {
+            InstrumentCsvError::Io(format!("Failed to open {}: {}", path.as_ref().display(), e))
+        })?;
+        Self::detect(file)
+    }
+
+    /// Wraps a plain or gzip-compressed stream, detected from its first bytes.
+    pub fn detect<R: Read + 'static>(reader: R) -> Result<Self, InstrumentCsvError> {
+        let mut reader = BufReader::new(reader);
+        let is_gzip = reader
+            .fill_buf()
+            .map_err(|e| InstrumentCsvError::Io(e.to_string()))?
+            .starts_with(&GZIP_MAGIC);
+
+        if is_gzip {
+            InstrumentCsvReader::new(Box::new(GzDecoder::new(reader)) as Box<dyn Read>)
+        } else {
+            InstrumentCsvReader::new(Box::new(reader) as Box<dyn Read>)
+        }
+    }
+}
+
+impl<R: Read> InstrumentCsvReader<R> {
+    /// Wraps an uncompressed CSV stream and reads its header row.
+    pub fn new(reader: R) -> Result<Self, InstrumentCsvError> {
+        let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(reader);
+        let headers = reader
+            .headers()
+            .map_err(|e| InstrumentCsvError::Io(format!("Failed to read CSV header: {}", e)))?
+            .clone();
+        let mut columns = HashMap::new();
+        for (index, header) in headers.iter().enumerate() {
+            columns.entry(header.to_string()).or_insert(index);
+        }
+
+        Ok(Self {
+            headers,
+            columns,
+            records: reader.into_records(),
+            aliases: IndexAliases::builtin().clone(),
+            finished: false,
+        })
+    }
+
+    /// Rewrites index names with `aliases` instead of the built-in ones.
+    pub fn with_aliases(mut self, aliases: IndexAliases) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
+    pub fn headers(&self) -> &StringRecord {
+        &self.headers
+    }
+
+    /// Reads the remaining rows, splitting them into instruments and row errors.
+    pub fn read_all(self) -> (Vec<Instrument>, Vec<InstrumentCsvError>) {
+        let mut instruments = Vec::new();
+        let mut errors = Vec::new();
+        for row in self {
+            match row {
+                Ok(instrument) => instruments.push(instrument),
+                Err(e) => errors.push(e),
+            }
+        }
+        (instruments, errors)
+    }
+}
+
+impl<R: Read> Iterator for InstrumentCsvReader<R> {
+    type Item = Result<Instrument, InstrumentCsvError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let record = match self.records.next()? {
+            Ok(record) => record,
+            Err(e) => {
+                let line = e.position().map(|p| p.line()).unwrap_or_default();
+                if e.is_io_error() {
+                    // The underlying stream is broken; later reads would fail the same way.
+                    self.finished = true;
+                    return Some(Err(InstrumentCsvError::Io(format!("line {}: {}", line, e))));
+                }
+                return Some(Err(InstrumentCsvError::Csv { line, message: e.to_string() }));
+            }
+        };
+
+        let line = record.position().map(|p| p.line()).unwrap_or_default();
+        let row = CsvRow {
+            columns: &self.columns,
+            record: &record,
+        };
+        Some(
+            Instrument::from_source_with(&row, &self.aliases)
+                .map_err(|error: InstrumentError| InstrumentCsvError::Row { line, error }),
+        )
+    }
+}
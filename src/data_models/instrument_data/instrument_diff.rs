@@ -0,0 +1,153 @@
+use crate::data_models::instrument_data::{Instrument, InstrumentMaster};
+use std::fmt;
+
+/// Instrument columns compared by `InstrumentDiff`. `instrument_token` is the key and
+/// `last_price` is a snapshot value, so neither is listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InstrumentField {
+    ExchangeToken,
+    Tradingsymbol,
+    Name,
+    Expiry,
+    Strike,
+    TickSize,
+    LotSize,
+    Exchange,
+    Segment,
+    InstrumentType,
+    BaseExchange,
+}
+
+impl InstrumentField {
+    /// Column name in the `instruments` table.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InstrumentField::ExchangeToken => "exchange_token",
+            InstrumentField::Tradingsymbol => "tradingsymbol",
+            InstrumentField::Name => "name",
+            InstrumentField::Expiry => "expiry",
+            InstrumentField::Strike => "strike",
+            InstrumentField::TickSize => "tick_size",
+            InstrumentField::LotSize => "lot_size",
+            InstrumentField::Exchange => "exchange",
+            InstrumentField::Segment => "segment",
+            InstrumentField::InstrumentType => "instrument_type",
+            InstrumentField::BaseExchange => "base_exchange",
+        }
+    }
+}
+
+impl fmt::Display for InstrumentField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One changed column, with both values rendered for logging.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: InstrumentField,
+    pub old: String,
+    pub new: String,
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {} -> {}", self.field, self.old, self.new)
+    }
+}
+
+/// An instrument present in both snapshots whose columns differ.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstrumentChange {
+    pub old: Instrument,
+    pub new: Instrument,
+    pub changes: Vec<FieldChange>,
+}
+
+impl InstrumentChange {
+    pub fn instrument_token(&self) -> &str {
+        &self.new.instrument_token
+    }
+
+    pub fn changed(&self, field: InstrumentField) -> bool {
+        self.changes.iter().any(|c| c.field == field)
+    }
+}
+
+/// Differences between two instrument snapshots, keyed by `instrument_token`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InstrumentDiff {
+    /// In the new snapshot only, in its order.
+    pub added: Vec<Instrument>,
+    /// In the old snapshot only, e.g. expired contracts, in its order.
+    pub removed: Vec<Instrument>,
+    pub modified: Vec<InstrumentChange>,
+}
+
+impl InstrumentDiff {
+    /// Compares yesterday's snapshot (`old`) with today's (`new`).
+    pub fn between(old: &InstrumentMaster, new: &InstrumentMaster) -> Self {
+        let mut diff = InstrumentDiff::default();
+
+        for instrument in new.iter() {
+            match old.by_token(&instrument.instrument_token) {
+                None => diff.added.push(instrument.clone()),
+                Some(previous) => {
+                    let changes = field_changes(previous, instrument);
+                    if !changes.is_empty() {
+                        diff.modified.push(InstrumentChange {
+                            old: previous.clone(),
+                            new: instrument.clone(),
+                            changes,
+                        });
+                    }
+                }
+            }
+        }
+
+        diff.removed = old
+            .iter()
+            .filter(|i| new.by_token(&i.instrument_token).is_none())
+            .cloned()
+            .collect();
+
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+
+    /// Modified instruments where `field` changed, e.g. `LotSize` for alerting.
+    pub fn changes_to(&self, field: InstrumentField) -> impl Iterator<Item = &InstrumentChange> {
+        self.modified.iter().filter(move |c| c.changed(field))
+    }
+}
+
+fn field_changes(old: &Instrument, new: &Instrument) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    let mut compare = |field, old: String, new: String| {
+        if old != new {
+            changes.push(FieldChange { field, old, new });
+        }
+    };
+
+    compare(InstrumentField::ExchangeToken, old.exchange_token.clone(), new.exchange_token.clone());
+    compare(InstrumentField::Tradingsymbol, old.tradingsymbol.clone(), new.tradingsymbol.clone());
+    compare(InstrumentField::Name, optional(&old.name), optional(&new.name));
+    compare(InstrumentField::Expiry, optional(&old.expiry), optional(&new.expiry));
+    compare(InstrumentField::Strike, old.strike.to_string(), new.strike.to_string());
+    compare(InstrumentField::TickSize, old.tick_size.to_string(), new.tick_size.to_string());
+    compare(InstrumentField::LotSize, old.lot_size.to_string(), new.lot_size.to_string());
+    compare(InstrumentField::Exchange, old.exchange.to_string(), new.exchange.to_string());
+    compare(InstrumentField::Segment, old.segment.to_string(), new.segment.to_string());
+    compare(InstrumentField::InstrumentType, old.instrument_type.to_string(), new.instrument_type.to_string());
+    compare(InstrumentField::BaseExchange, old.base_exchange.to_string(), new.base_exchange.to_string());
+
+    changes
+}
+
+fn optional<T: ToString>(value: &Option<T>) -> String {
+    value.as_ref().map(ToString::to_string).unwrap_or_default()
+}
@@ -0,0 +1,138 @@
+use crate::data_models::instrument_data::{Exchange, Instrument, InstrumentType};
+use chrono::NaiveDate;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+/// Positions of one underlying's instruments. Keyed per name so lookups borrow the `&str`.
+#[derive(Debug, Default)]
+struct NameIndex {
+    all: Vec<usize>,
+    by_expiry: BTreeMap<NaiveDate, Vec<usize>>,
+    by_type: HashMap<InstrumentType, Vec<usize>>,
+}
+
+#[derive(Debug, Default)]
+struct MasterIndex {
+    instruments: Vec<Instrument>,
+    by_token: HashMap<String, usize>,
+    by_symbol: HashMap<Exchange, HashMap<String, usize>>,
+    by_exchange_token: HashMap<Exchange, HashMap<String, usize>>,
+    by_name: HashMap<String, NameIndex>,
+}
+
+/// Read-only, indexed view over the instruments table.
+///
+/// Cloning is cheap: all clones share the same instruments and indexes, so one master
+/// can be handed to every task that needs it.
+#[derive(Debug, Clone, Default)]
+pub struct InstrumentMaster {
+    index: Arc<MasterIndex>,
+}
+
+impl InstrumentMaster {
+    /// Builds the indexes. If an `instrument_token` appears more than once, the last row wins.
+    pub fn new<I: IntoIterator<Item = Instrument>>(instruments: I) -> Self {
+        let mut index = MasterIndex::default();
+
+        for instrument in instruments {
+            match index.by_token.get(&instrument.instrument_token) {
+                Some(&pos) => index.instruments[pos] = instrument,
+                None => {
+                    index.by_token.insert(instrument.instrument_token.clone(), index.instruments.len());
+                    index.instruments.push(instrument);
+                }
+            }
+        }
+
+        for (pos, instrument) in index.instruments.iter().enumerate() {
+            index
+                .by_symbol
+                .entry(instrument.exchange)
+                .or_default()
+                .insert(instrument.tradingsymbol.clone(), pos);
+            index
+                .by_exchange_token
+                .entry(instrument.exchange)
+                .or_default()
+                .insert(instrument.exchange_token.clone(), pos);
+            if let Some(name) = &instrument.name {
+                let by_name = index.by_name.entry(name.clone()).or_default();
+                by_name.all.push(pos);
+                if let Some(expiry) = instrument.expiry {
+                    by_name.by_expiry.entry(expiry).or_default().push(pos);
+                }
+                by_name.by_type.entry(instrument.instrument_type).or_default().push(pos);
+            }
+        }
+
+        Self { index: Arc::new(index) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.instruments.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.instruments.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Instrument> {
+        self.index.instruments.iter()
+    }
+
+    pub fn by_token(&self, instrument_token: &str) -> Option<&Instrument> {
+        self.get(self.index.by_token.get(instrument_token))
+    }
+
+    /// Looks up e.g. `(Exchange::Nfo, "NIFTY24DECFUT")`.
+    pub fn by_symbol(&self, exchange: Exchange, tradingsymbol: &str) -> Option<&Instrument> {
+        self.get(self.index.by_symbol.get(&exchange)?.get(tradingsymbol))
+    }
+
+    /// Exchange tokens are only unique within an exchange, so the exchange is part of the key.
+    pub fn by_exchange_token(&self, exchange: Exchange, exchange_token: &str) -> Option<&Instrument> {
+        self.get(self.index.by_exchange_token.get(&exchange)?.get(exchange_token))
+    }
+
+    /// Every instrument whose underlying `name` matches, e.g. all NIFTY futures and options.
+    pub fn by_name<'a>(&'a self, name: &str) -> impl Iterator<Item = &'a Instrument> + 'a {
+        self.positions(self.index.by_name.get(name).map(|n| &n.all))
+    }
+
+    pub fn by_expiry<'a>(&'a self, name: &str, expiry: NaiveDate) -> impl Iterator<Item = &'a Instrument> + 'a {
+        self.positions(self.index.by_name.get(name).and_then(|n| n.by_expiry.get(&expiry)))
+    }
+
+    pub fn by_type<'a>(
+        &'a self,
+        name: &str,
+        instrument_type: InstrumentType,
+    ) -> impl Iterator<Item = &'a Instrument> + 'a {
+        self.positions(self.index.by_name.get(name).and_then(|n| n.by_type.get(&instrument_type)))
+    }
+
+    /// Distinct expiries listed for the underlying, earliest first.
+    pub fn expiries(&self, name: &str) -> Vec<NaiveDate> {
+        self.index
+            .by_name
+            .get(name)
+            .map_or_else(Vec::new, |n| n.by_expiry.keys().copied().collect())
+    }
+
+    fn get(&self, pos: Option<&usize>) -> Option<&Instrument> {
+        pos.map(|&pos| &self.index.instruments[pos])
+    }
+
+    fn positions<'a>(&'a self, positions: Option<&'a Vec<usize>>) -> impl Iterator<Item = &'a Instrument> + 'a {
+        positions
+            .into_iter()
+            .flatten()
+            .map(|&pos| &self.index.instruments[pos])
+    }
+}
+
+impl FromIterator<Instrument> for InstrumentMaster {
+    fn from_iter<I: IntoIterator<Item = Instrument>>(iter: I) -> Self {
+        Self::new(iter)
+    }
+}
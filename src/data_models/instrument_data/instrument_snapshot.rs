@@ -0,0 +1,77 @@
+use crate::data_models::instrument_data::{BaseExchange, Exchange, Instrument, InstrumentType, Segment};
+use crate::data_models::clickhouse_table;
+use chrono::NaiveDate;
+
+clickhouse_table! {
+    #[clickhouse_table(
+        name = "instrument_snapshots",
+        engine = "ReplacingMergeTree()",
+        partition_by = "toYYYYMM(snapshot_date)",
+        order_by = "(snapshot_date, instrument_token)",
+    )]
+    /// A row of `instrument_snapshots`: an `Instrument` as listed on `snapshot_date`.
+    #[derive(Debug, Clone, PartialEq, clickhouse::Row, serde::Serialize, serde::Deserialize)]
+    pub struct InstrumentSnapshot {
+        #[serde(with = "clickhouse::serde::chrono::date")]
+        pub snapshot_date: NaiveDate,
+        pub instrument_token: String,
+        pub exchange_token: String,
+        pub tradingsymbol: String,
+        pub name: Option<String>,
+        pub last_price: f64,
+        #[serde(with = "clickhouse::serde::chrono::date::option")]
+        pub expiry: Option<NaiveDate>,
+        pub strike: f64,
+        pub tick_size: f64,
+        pub lot_size: u32,
+        pub exchange: Exchange,
+        pub segment: Segment,
+        pub instrument_type: InstrumentType,
+        pub base_exchange: BaseExchange,
+    }
+}
+
+impl InstrumentSnapshot {
+    pub fn new(snapshot_date: NaiveDate, instrument: Instrument) -> Self {
+        Self {
+            snapshot_date,
+            instrument_token: instrument.instrument_token,
+            exchange_token: instrument.exchange_token,
+            tradingsymbol: instrument.tradingsymbol,
+            name: instrument.name,
+            last_price: instrument.last_price,
+            expiry: instrument.expiry,
+            strike: instrument.strike,
+            tick_size: instrument.tick_size,
+            lot_size: instrument.lot_size,
+            exchange: instrument.exchange,
+            segment: instrument.segment,
+            instrument_type: instrument.instrument_type,
+            base_exchange: instrument.base_exchange,
+        }
+    }
+
+    pub fn into_instrument(self) -> Instrument {
+        Instrument {
+            instrument_token: self.instrument_token,
+            exchange_token: self.exchange_token,
+            tradingsymbol: self.tradingsymbol,
+            name: self.name,
+            last_price: self.last_price,
+            expiry: self.expiry,
+            strike: self.strike,
+            tick_size: self.tick_size,
+            lot_size: self.lot_size,
+            exchange: self.exchange,
+            segment: self.segment,
+            instrument_type: self.instrument_type,
+            base_exchange: self.base_exchange,
+        }
+    }
+}
+
+impl From<InstrumentSnapshot> for Instrument {
+    fn from(snapshot: InstrumentSnapshot) -> Self {
+        snapshot.into_instrument()
+    }
+}
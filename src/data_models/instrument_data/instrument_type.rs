@@ -1,5 +1,7 @@
+use crate::data_models::{clickhouse_enum, kite_enum};
 
 #[derive(Debug, serde_repr::Serialize_repr, serde_repr::Deserialize_repr)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(i8)]
 pub enum InstrumentType {
     Eq = 0,
@@ -8,4 +10,11 @@ pub enum InstrumentType {
     Pe = 3,
 }
 
+kite_enum!(#[repr(i8)] InstrumentType, "instrument type", {
+    Eq => "EQ",
+    Fut => "FUT",
+    Ce => "CE",
+    Pe => "PE",
+});
 
+clickhouse_enum!(InstrumentType { Eq, Fut, Ce, Pe });
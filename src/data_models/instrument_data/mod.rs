@@ -1,12 +1,26 @@
 mod instrument;
+mod instrument_csv;
+mod instrument_master;
+mod instrument_diff;
+mod instrument_snapshot;
+mod option_chain;
 mod exchange;
 mod instrument_type;
 mod segment;
 mod base_exchange;
+mod error;
+mod index_aliases;
 pub mod queries;
 
 pub use instrument::*;
+pub use instrument_csv::*;
+pub use instrument_master::*;
+pub use instrument_diff::*;
+pub use instrument_snapshot::*;
+pub use option_chain::*;
 pub use exchange::*;
 pub use instrument_type::*;
 pub use segment::*;
 pub use base_exchange::*;
+pub use error::*;
+pub use index_aliases::*;
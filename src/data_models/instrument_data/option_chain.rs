@@ -0,0 +1,139 @@
+use crate::data_models::instrument_data::{Instrument, InstrumentMaster, InstrumentType};
+use crate::data_models::strike_ladder::StrikeLadder;
+use chrono::NaiveDate;
+
+/// The call and put listed at one strike. Either side may be missing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrikeRow {
+    pub strike: f64,
+    pub call: Option<Instrument>,
+    pub put: Option<Instrument>,
+}
+
+/// CE/PE pairs for one underlying and expiry, ordered by strike.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionChain {
+    name: String,
+    expiry: NaiveDate,
+    lot_size: u32,
+    tick_size: f64,
+    rows: Vec<StrikeRow>,
+    /// The rows' strikes, index for index.
+    ladder: StrikeLadder,
+}
+
+impl OptionChain {
+    /// Picks the CE and PE instruments for `name` and `expiry` out of `instruments`.
+    /// Returns `None` if there are none.
+    pub fn new<'a, I>(name: &str, expiry: NaiveDate, instruments: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = &'a Instrument>,
+    {
+        let mut options: Vec<&Instrument> = instruments
+            .into_iter()
+            .filter(|i| matches!(i.instrument_type, InstrumentType::Ce | InstrumentType::Pe))
+            .filter(|i| i.name.as_deref() == Some(name) && i.expiry == Some(expiry))
+            .filter(|i| i.strike.is_finite())
+            .collect();
+        options.sort_by(|a, b| a.strike.total_cmp(&b.strike));
+
+        let first = options.first()?;
+        let (lot_size, tick_size) = (first.lot_size, first.tick_size);
+
+        let mut rows: Vec<StrikeRow> = Vec::new();
+        for option in options {
+            let row = match rows.last_mut() {
+                Some(row) if row.strike == option.strike => row,
+                _ => {
+                    rows.push(StrikeRow {
+                        strike: option.strike,
+                        call: None,
+                        put: None,
+                    });
+                    rows.last_mut().unwrap()
+                }
+            };
+            match option.instrument_type {
+                InstrumentType::Ce => row.call = Some(option.clone()),
+                _ => row.put = Some(option.clone()),
+            }
+        }
+
+        let ladder = StrikeLadder::new(rows.iter().map(|row| row.strike).collect());
+        Some(Self {
+            name: name.to_string(),
+            expiry,
+            lot_size,
+            tick_size,
+            rows,
+            ladder,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn expiry(&self) -> NaiveDate {
+        self.expiry
+    }
+
+    pub fn lot_size(&self) -> u32 {
+        self.lot_size
+    }
+
+    pub fn tick_size(&self) -> f64 {
+        self.tick_size
+    }
+
+    pub fn rows(&self) -> &[StrikeRow] {
+        &self.rows
+    }
+
+    /// The strike ladder, lowest first.
+    pub fn strikes(&self) -> Vec<f64> {
+        self.ladder.strikes().to_vec()
+    }
+
+    pub fn ladder(&self) -> &StrikeLadder {
+        &self.ladder
+    }
+
+    pub fn row(&self, strike: f64) -> Option<&StrikeRow> {
+        self.ladder.index_of(strike).map(|i| &self.rows[i])
+    }
+
+    pub fn call(&self, strike: f64) -> Option<&Instrument> {
+        self.row(strike)?.call.as_ref()
+    }
+
+    pub fn put(&self, strike: f64) -> Option<&Instrument> {
+        self.row(strike)?.put.as_ref()
+    }
+
+    /// The listed strike closest to `spot`. Ties go to the lower strike.
+    pub fn atm_strike(&self, spot: f64) -> Option<f64> {
+        self.ladder.atm(spot)
+    }
+
+    /// The ATM row and up to `n` rows either side of it.
+    pub fn around_atm(&self, spot: f64, n: usize) -> &[StrikeRow] {
+        &self.rows[self.ladder.around_atm_range(spot, n)]
+    }
+}
+
+impl InstrumentMaster {
+    /// Option chain for the underlying at `expiry`.
+    pub fn option_chain(&self, name: &str, expiry: NaiveDate) -> Option<OptionChain> {
+        OptionChain::new(name, expiry, self.by_expiry(name, expiry))
+    }
+
+    /// Earliest option expiry on or after `date`, e.g. the next weekly expiry.
+    pub fn next_option_expiry(&self, name: &str, date: NaiveDate) -> Option<NaiveDate> {
+        self.by_name(name)
+            .filter(|i| matches!(i.instrument_type, InstrumentType::Ce | InstrumentType::Pe))
+            .filter_map(|i| i.expiry)
+            .filter(|expiry| *expiry >= date)
+            .min()
+    }
+}
@@ -0,0 +1,48 @@
+use crate::data_models::SchemaTarget;
+
+static SELECT_SNAPSHOT_DATE_AS_OF: &str = r#"
+SELECT max(snapshot_date)
+FROM ${instrument_snapshots}
+WHERE snapshot_date <= ?
+"#;
+
+static SELECT_INSTRUMENTS_AS_OF: &str = r#"
+SELECT ?fields
+FROM ${instrument_snapshots} FINAL
+WHERE snapshot_date = (
+    SELECT max(snapshot_date)
+    FROM ${instrument_snapshots}
+    WHERE snapshot_date <= ?
+)
+"#;
+
+static SELECT_INSTRUMENT_HISTORY: &str = r#"
+SELECT ?fields
+FROM ${instrument_snapshots} FINAL
+WHERE instrument_token = ?
+ORDER BY snapshot_date
+"#;
+
+static DELETE_SNAPSHOT: &str = r#"
+ALTER TABLE ${instrument_snapshots} DELETE WHERE snapshot_date = ?
+"#;
+
+/// Snapshot date in effect on `?`: the latest one on or before it.
+pub fn select_snapshot_date_as_of(target: &SchemaTarget) -> String {
+    target.render(SELECT_SNAPSHOT_DATE_AS_OF)
+}
+
+/// Instruments as listed on date `?`, for backtests. Use with `fetch_all::<InstrumentSnapshot>()`.
+pub fn select_instruments_as_of(target: &SchemaTarget) -> String {
+    target.render(SELECT_INSTRUMENTS_AS_OF)
+}
+
+/// Every stored version of instrument `?`, oldest first.
+pub fn select_instrument_history(target: &SchemaTarget) -> String {
+    target.render(SELECT_INSTRUMENT_HISTORY)
+}
+
+/// Drops one day's snapshot, e.g. before re-ingesting it from a corrected dump.
+pub fn delete_snapshot(target: &SchemaTarget) -> String {
+    target.render(DELETE_SNAPSHOT)
+}
@@ -1,3 +1,5 @@
 mod table_creation;
+mod instrument_snapshots;
 
-pub use table_creation::CREATE_INSTRUMENTS_TABLE;
+pub use table_creation::*;
+pub use instrument_snapshots::*;
@@ -1,5 +1,8 @@
+use crate::data_models::{clickhouse_enum, kite_enum};
+use crate::data_models::instrument_data::Exchange;
 
 #[derive(Debug, serde_repr::Serialize_repr, serde_repr::Deserialize_repr)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(i8)]
 pub enum Segment {
     BcdFut = 0,
@@ -20,3 +23,61 @@ pub enum Segment {
     Nse = 15,
 }
 
+kite_enum!(#[repr(i8)] Segment, "segment", {
+    BcdFut => "BCD-FUT",
+    BcdOpt => "BCD-OPT",
+    BfoFut => "BFO-FUT",
+    BfoOpt => "BFO-OPT",
+    Bse => "BSE",
+    CdsFut => "CDS-FUT",
+    CdsOpt => "CDS-OPT",
+    Indices => "INDICES",
+    McxFut => "MCX-FUT",
+    McxOpt => "MCX-OPT",
+    Nco => "NCO",
+    NcoFut => "NCO-FUT",
+    NcoOpt => "NCO-OPT",
+    NfoFut => "NFO-FUT",
+    NfoOpt => "NFO-OPT",
+    Nse => "NSE",
+});
+
+clickhouse_enum!(Segment {
+    BcdFut, BcdOpt, BfoFut, BfoOpt, Bse, CdsFut, CdsOpt, Indices,
+    McxFut, McxOpt, Nco, NcoFut, NcoOpt, NfoFut, NfoOpt, Nse,
+});
+
+impl Segment {
+    /// The exchange this segment belongs to. `None` for `INDICES`, which Kite lists under several exchanges.
+    pub fn exchange(&self) -> Option<Exchange> {
+        match self {
+            Segment::BcdFut | Segment::BcdOpt => Some(Exchange::Bcd),
+            Segment::BfoFut | Segment::BfoOpt => Some(Exchange::Bfo),
+            Segment::Bse => Some(Exchange::Bse),
+            Segment::CdsFut | Segment::CdsOpt => Some(Exchange::Cds),
+            Segment::Indices => None,
+            Segment::McxFut | Segment::McxOpt => Some(Exchange::Mcx),
+            Segment::Nco | Segment::NcoFut | Segment::NcoOpt => Some(Exchange::Nco),
+            Segment::NfoFut | Segment::NfoOpt => Some(Exchange::Nfo),
+            Segment::Nse => Some(Exchange::Nse),
+        }
+    }
+
+    pub fn is_future(&self) -> bool {
+        matches!(
+            self,
+            Segment::BcdFut | Segment::BfoFut | Segment::CdsFut | Segment::McxFut | Segment::NcoFut | Segment::NfoFut
+        )
+    }
+
+    pub fn is_option(&self) -> bool {
+        matches!(
+            self,
+            Segment::BcdOpt | Segment::BfoOpt | Segment::CdsOpt | Segment::McxOpt | Segment::NcoOpt | Segment::NfoOpt
+        )
+    }
+
+    pub fn is_derivative(&self) -> bool {
+        self.is_future() || self.is_option()
+    }
+}
@@ -0,0 +1,51 @@
+/// Implements `as_str`, `FromStr` and `Display` for an enum from its Kite wire names, plus
+/// `TryFrom<i8>` when marked `#[repr(i8)]`. Every variant must be listed, or `as_str` fails
+/// to compile.
+macro_rules! kite_enum {
+    ($ty:ident, $kind:literal, { $($variant:ident => $name:literal),+ $(,)? }) => {
+        impl $ty {
+            /// Kite wire name.
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $($ty::$variant => $name,)+
+                }
+            }
+        }
+
+        impl std::str::FromStr for $ty {
+            type Err = $crate::data_models::ParseEnumError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $($name => Ok($ty::$variant),)+
+                    _ => Err($crate::data_models::ParseEnumError::new($kind, s)),
+                }
+            }
+        }
+
+        impl std::fmt::Display for $ty {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+    };
+
+    (#[repr(i8)] $ty:ident, $kind:literal, { $($variant:ident => $name:literal),+ $(,)? }) => {
+        $crate::data_models::kite_enum!($ty, $kind, { $($variant => $name),+ });
+
+        impl TryFrom<i8> for $ty {
+            type Error = $crate::data_models::ParseEnumError;
+
+            fn try_from(value: i8) -> Result<Self, Self::Error> {
+                $(
+                    if value == $ty::$variant as i8 {
+                        return Ok($ty::$variant);
+                    }
+                )+
+                Err($crate::data_models::ParseEnumError::new($kind, value))
+            }
+        }
+    };
+}
+
+pub(crate) use kite_enum;
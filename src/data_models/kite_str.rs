@@ -0,0 +1,134 @@
+//! Opt-in string serde for enums that default to their integer ClickHouse encoding.
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Quote {
+//!     #[serde(with = "kite_str")]
+//!     exchange: Exchange, // "NFO" instead of 6
+//! }
+//! ```
+use serde::{de, Deserialize, Deserializer, Serializer};
+use std::fmt::Display;
+use std::str::FromStr;
+
+pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Display,
+{
+    serializer.collect_str(value)
+}
+
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let name = String::deserialize(deserializer)?;
+    name.parse().map_err(de::Error::custom)
+}
+
+/// `Option<T>` as a Kite name or `null`.
+pub mod option {
+    use super::*;
+
+    pub fn serialize<S, T>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Display,
+    {
+        match value {
+            Some(value) => serializer.collect_str(value),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: FromStr,
+        T::Err: Display,
+    {
+        Option::<String>::deserialize(deserializer)?
+            .map(|name| name.parse().map_err(de::Error::custom))
+            .transpose()
+    }
+}
+
+/// `Vec<T>` as a list of Kite names; any unknown name is an error.
+pub mod vec {
+    use super::*;
+
+    pub fn serialize<S, T>(values: &[T], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Display,
+    {
+        serializer.collect_seq(values.iter().map(|v| v.to_string()))
+    }
+
+    pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: FromStr,
+        T::Err: Display,
+    {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|name| name.parse().map_err(de::Error::custom))
+            .collect()
+    }
+}
+
+/// A Kite name parsed as `T`, or kept as sent when this crate doesn't model it (e.g. the `MF`
+/// exchange or `BO`/`CO` products), so it survives being stored and read back.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum KiteName<T> {
+    Known(T),
+    Other(String),
+}
+
+impl<T> KiteName<T> {
+    pub fn known(&self) -> Option<&T> {
+        match self {
+            KiteName::Known(value) => Some(value),
+            KiteName::Other(_) => None,
+        }
+    }
+}
+
+impl<T> From<T> for KiteName<T> {
+    fn from(value: T) -> Self {
+        KiteName::Known(value)
+    }
+}
+
+impl<T: FromStr> FromStr for KiteName<T> {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(s.parse().map_or_else(|_| KiteName::Other(s.to_string()), KiteName::Known))
+    }
+}
+
+impl<T: Display> Display for KiteName<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            KiteName::Known(value) => value.fmt(f),
+            KiteName::Other(name) => f.write_str(name),
+        }
+    }
+}
+
+impl<T: Display> serde::Serialize for KiteName<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(self, serializer)
+    }
+}
+
+impl<'de, T: FromStr> Deserialize<'de> for KiteName<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize(deserializer)
+    }
+}
@@ -0,0 +1,314 @@
+use crate::data_models::futures_data::FuturesData;
+use crate::data_models::hist_data::{HistData, HistDataMaxDatetime};
+use crate::data_models::instrument_data::{Instrument, InstrumentSnapshot};
+use crate::data_models::migrations::MigrationError;
+use crate::data_models::{ClickHouseTable, SchemaTarget};
+use clickhouse::Client;
+use std::fmt;
+
+/// The schema a table should have, taken from its row type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpectedTable {
+    pub database: String,
+    pub name: String,
+    pub columns: Vec<(String, String)>,
+    pub engine: String,
+    /// Empty when the table is not partitioned, as `system.tables` reports it.
+    pub partition_key: String,
+    pub sorting_key: String,
+}
+
+impl ExpectedTable {
+    pub fn of<T: ClickHouseTable>(target: &SchemaTarget) -> Self {
+        Self::named::<T>(target, T::TABLE)
+    }
+
+    /// `T`'s schema under another name, e.g. `futures_staging`.
+    pub fn named<T: ClickHouseTable>(target: &SchemaTarget, table: &str) -> Self {
+        Self {
+            database: target.database().to_string(),
+            name: target.table_name(table),
+            columns: T::columns()
+                .into_iter()
+                .map(|(name, ty)| (name.to_string(), ty))
+                .collect(),
+            engine: T::ENGINE.to_string(),
+            partition_key: T::PARTITION_BY.unwrap_or_default().to_string(),
+            sorting_key: T::ORDER_BY.to_string(),
+        }
+    }
+
+    /// `database.name`.
+    pub fn full_name(&self) -> String {
+        format!("{}.{}", self.database, self.name)
+    }
+}
+
+/// Tables checked by `check_schema_drift`.
+pub fn expected_tables(target: &SchemaTarget) -> Vec<ExpectedTable> {
+    vec![
+        ExpectedTable::of::<Instrument>(target),
+        ExpectedTable::of::<InstrumentSnapshot>(target),
+        ExpectedTable::of::<FuturesData>(target),
+        ExpectedTable::named::<FuturesData>(target, "futures_staging"),
+        ExpectedTable::of::<HistData>(target),
+        ExpectedTable::of::<HistDataMaxDatetime>(target),
+    ]
+}
+
+/// A table as reported by `system.tables` and `system.columns`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiveTable {
+    pub engine: String,
+    pub partition_key: String,
+    pub sorting_key: String,
+    pub columns: Vec<(String, String)>,
+}
+
+#[derive(Debug, clickhouse::Row, serde::Deserialize)]
+struct LiveTableRow {
+    engine: String,
+    partition_key: String,
+    sorting_key: String,
+}
+
+#[derive(Debug, clickhouse::Row, serde::Deserialize)]
+struct LiveColumnRow {
+    name: String,
+    column_type: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DriftIssue {
+    MissingTable,
+    MissingColumn { column: String, expected: String },
+    UnexpectedColumn { column: String, actual: String },
+    TypeMismatch { column: String, expected: String, actual: String },
+    /// Both sides are `Enum8` but their values differ.
+    EnumMismatch { column: String, missing: Vec<String>, unexpected: Vec<String> },
+    EngineMismatch { expected: String, actual: String },
+    PartitionKeyMismatch { expected: String, actual: String },
+    SortKeyMismatch { expected: String, actual: String },
+}
+
+impl fmt::Display for DriftIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DriftIssue::MissingTable => write!(f, "table does not exist"),
+            DriftIssue::MissingColumn { column, expected } => write!(f, "missing column {} {}", column, expected),
+            DriftIssue::UnexpectedColumn { column, actual } => write!(f, "unexpected column {} {}", column, actual),
+            DriftIssue::TypeMismatch { column, expected, actual } => {
+                write!(f, "column {} is {}, expected {}", column, actual, expected)
+            }
+            DriftIssue::EnumMismatch { column, missing, unexpected } => write!(
+                f,
+                "column {} enum values differ: missing [{}], unexpected [{}]",
+                column,
+                missing.join(", "),
+                unexpected.join(", ")
+            ),
+            DriftIssue::EngineMismatch { expected, actual } => write!(f, "engine is {}, expected {}", actual, expected),
+            DriftIssue::PartitionKeyMismatch { expected, actual } => {
+                write!(f, "partition key is ({}), expected ({})", actual, expected)
+            }
+            DriftIssue::SortKeyMismatch { expected, actual } => {
+                write!(f, "sort key is ({}), expected ({})", actual, expected)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableDrift {
+    pub table: String,
+    pub issues: Vec<DriftIssue>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DriftReport {
+    /// Only tables with at least one issue.
+    pub tables: Vec<TableDrift>,
+}
+
+impl DriftReport {
+    pub fn is_clean(&self) -> bool {
+        self.tables.is_empty()
+    }
+}
+
+impl fmt::Display for DriftReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_clean() {
+            return write!(f, "No schema drift");
+        }
+        for table in &self.tables {
+            for issue in &table.issues {
+                writeln!(f, "{}: {}", table.table, issue)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Compares one table's expected schema with what the server reports (`None` if it doesn't exist).
+pub fn compare_table(expected: &ExpectedTable, live: Option<&LiveTable>) -> TableDrift {
+    let mut issues = Vec::new();
+
+    match live {
+        None => issues.push(DriftIssue::MissingTable),
+        Some(live) => {
+            for (column, expected_type) in &expected.columns {
+                match live.columns.iter().find(|(name, _)| name == column) {
+                    None => issues.push(DriftIssue::MissingColumn {
+                        column: column.clone(),
+                        expected: expected_type.clone(),
+                    }),
+                    Some((_, actual)) if normalize(actual) != normalize(expected_type) => {
+                        issues.push(type_issue(column, expected_type, actual))
+                    }
+                    Some(_) => {}
+                }
+            }
+            for (column, actual) in &live.columns {
+                if !expected.columns.iter().any(|(name, _)| name == column) {
+                    issues.push(DriftIssue::UnexpectedColumn {
+                        column: column.clone(),
+                        actual: actual.clone(),
+                    });
+                }
+            }
+
+            let expected_engine = engine_name(&expected.engine);
+            if expected_engine != live.engine {
+                issues.push(DriftIssue::EngineMismatch {
+                    expected: expected_engine.to_string(),
+                    actual: live.engine.clone(),
+                });
+            }
+            let expected_partition = sort_key(&expected.partition_key);
+            if expected_partition != sort_key(&live.partition_key) {
+                issues.push(DriftIssue::PartitionKeyMismatch {
+                    expected: expected_partition,
+                    actual: live.partition_key.clone(),
+                });
+            }
+            let expected_key = sort_key(&expected.sorting_key);
+            if expected_key != sort_key(&live.sorting_key) {
+                issues.push(DriftIssue::SortKeyMismatch {
+                    expected: expected_key,
+                    actual: live.sorting_key.clone(),
+                });
+            }
+        }
+    }
+
+    TableDrift {
+        table: expected.full_name(),
+        issues,
+    }
+}
+
+/// Reads the live schema of every table in `expected_tables(target)` and reports differences.
+pub async fn check_schema_drift(client: &Client, target: &SchemaTarget) -> Result<DriftReport, MigrationError> {
+    check_tables(client, &expected_tables(target)).await
+}
+
+pub async fn check_tables(client: &Client, tables: &[ExpectedTable]) -> Result<DriftReport, MigrationError> {
+    let mut report = DriftReport::default();
+    for expected in tables {
+        let live = read_live_table(client, expected).await?;
+        let drift = compare_table(expected, live.as_ref());
+        if !drift.issues.is_empty() {
+            report.tables.push(drift);
+        }
+    }
+    Ok(report)
+}
+
+async fn read_live_table(client: &Client, table: &ExpectedTable) -> Result<Option<LiveTable>, MigrationError> {
+    let row = client
+        .query(
+            "SELECT engine, partition_key, sorting_key FROM system.tables \
+             WHERE database = ? AND name = ?",
+        )
+        .bind(&table.database)
+        .bind(&table.name)
+        .fetch_optional::<LiveTableRow>()
+        .await?;
+    let Some(row) = row else {
+        return Ok(None);
+    };
+
+    let columns = client
+        .query(
+            "SELECT name, type AS column_type FROM system.columns \
+             WHERE database = ? AND table = ? ORDER BY position",
+        )
+        .bind(&table.database)
+        .bind(&table.name)
+        .fetch_all::<LiveColumnRow>()
+        .await?;
+
+    Ok(Some(LiveTable {
+        engine: row.engine,
+        partition_key: row.partition_key,
+        sorting_key: row.sorting_key,
+        columns: columns.into_iter().map(|c| (c.name, c.column_type)).collect(),
+    }))
+}
+
+fn type_issue(column: &str, expected: &str, actual: &str) -> DriftIssue {
+    if let (Some(expected_values), Some(actual_values)) = (enum_values(expected), enum_values(actual)) {
+        return DriftIssue::EnumMismatch {
+            column: column.to_string(),
+            missing: expected_values
+                .iter()
+                .filter(|v| !actual_values.contains(v))
+                .cloned()
+                .collect(),
+            unexpected: actual_values
+                .iter()
+                .filter(|v| !expected_values.contains(v))
+                .cloned()
+                .collect(),
+        };
+    }
+    DriftIssue::TypeMismatch {
+        column: column.to_string(),
+        expected: expected.to_string(),
+        actual: actual.to_string(),
+    }
+}
+
+/// Values of an `Enum8(...)` type, rendered as `'Day' = 0`.
+fn enum_values(ty: &str) -> Option<Vec<String>> {
+    let normalized = normalize(ty);
+    let inner = normalized.strip_prefix("Enum8(")?.strip_suffix(')')?;
+    inner
+        .split(",'")
+        .map(|entry| {
+            let (name, value) = entry.trim_start_matches('\'').split_once("'=")?;
+            Some(format!("'{}' = {}", name, value))
+        })
+        .collect()
+}
+
+/// Drops whitespace so `Enum8('a' = 1, 'b' = 2)` and `Enum8('a'=1,'b'=2)` compare equal.
+fn normalize(ty: &str) -> String {
+    ty.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+/// `ReplacingMergeTree()` → `ReplacingMergeTree`, as `system.tables` reports it.
+fn engine_name(engine: &str) -> &str {
+    engine.split('(').next().unwrap_or(engine).trim()
+}
+
+/// `(a, b)` and `a,b` → `a, b`; used for partition and sort keys.
+fn sort_key(key: &str) -> String {
+    let key = key.trim();
+    let key = key
+        .strip_prefix('(')
+        .and_then(|k| k.strip_suffix(')'))
+        .unwrap_or(key);
+    key.split(',').map(str::trim).collect::<Vec<_>>().join(", ")
+}
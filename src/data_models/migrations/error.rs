@@ -0,0 +1,40 @@
+use std::fmt;
+
+/// **Schema Migration Errors**
+#[derive(Debug)]
+pub enum MigrationError {
+    ClickHouse(clickhouse::error::Error),
+    /// Two migrations share a version, or versions are not in ascending order.
+    InvalidOrder(u32),
+    /// An applied migration's SQL was edited after it ran.
+    ChecksumMismatch { version: u32, name: String },
+}
+
+impl fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MigrationError::ClickHouse(e) => write!(f, "ClickHouse error: {}", e),
+            MigrationError::InvalidOrder(version) => {
+                write!(f, "Migration {} is duplicated or out of order", version)
+            }
+            MigrationError::ChecksumMismatch { version, name } => {
+                write!(f, "Migration {} ({}) was modified after it was applied", version, name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MigrationError::ClickHouse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<clickhouse::error::Error> for MigrationError {
+    fn from(e: clickhouse::error::Error) -> Self {
+        MigrationError::ClickHouse(e)
+    }
+}
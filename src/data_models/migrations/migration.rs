@@ -0,0 +1,207 @@
+use crate::data_models::clickhouse_table;
+use crate::data_models::migrations::MigrationError;
+use crate::data_models::SchemaTarget;
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// A versioned schema change. `sql` may hold several statements separated by `;`, and refers
+/// to tables as `${name}` so `SchemaTarget` can qualify them. A `;` inside a quoted literal or
+/// identifier, or a comment, does not end a statement.
+///
+/// Never edit a migration once it has been applied anywhere; add a new one instead.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+impl Migration {
+    pub const fn new(version: u32, name: &'static str, sql: &'static str) -> Self {
+        Self { version, name, sql }
+    }
+
+    /// Statements in the order they run, with table names qualified for `target`.
+    pub fn statements(&self, target: &SchemaTarget) -> Vec<String> {
+        split_statements(self.sql)
+            .into_iter()
+            .map(|s| target.render(s))
+            .collect()
+    }
+
+    /// SHA-256 of the SQL before rendering, hex-encoded, so it is the same for every target.
+    pub fn checksum(&self) -> String {
+        hex::encode(Sha256::digest(self.sql.as_bytes()))
+    }
+}
+
+/// Splits on `;` outside `'...'`, `"..."`, `` `...` ``, `-- ...` and `/* ... */`. Statements are
+/// trimmed, and those holding only comments are dropped.
+fn split_statements(sql: &str) -> Vec<&str> {
+    let mut statements = Vec::new();
+    let mut start = 0;
+    let mut has_code = false;
+    let mut chars = sql.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        let next = chars.peek().map(|&(_, n)| n);
+        match c {
+            '\'' | '"' | '`' => {
+                has_code = true;
+                while let Some((_, q)) = chars.next() {
+                    if q == '\\' {
+                        chars.next();
+                    } else if q == c {
+                        break;
+                    }
+                }
+            }
+            '-' if next == Some('-') => {
+                for (_, q) in chars.by_ref() {
+                    if q == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if next == Some('*') => {
+                chars.next();
+                let mut prev = ' ';
+                for (_, q) in chars.by_ref() {
+                    if prev == '*' && q == '/' {
+                        break;
+                    }
+                    prev = q;
+                }
+            }
+            ';' => {
+                if has_code {
+                    statements.push(sql[start..i].trim());
+                }
+                start = i + 1;
+                has_code = false;
+            }
+            c if !c.is_whitespace() => has_code = true,
+            _ => {}
+        }
+    }
+    if has_code {
+        statements.push(sql[start..].trim());
+    }
+    statements
+}
+
+/// Migrations embedded in the crate, oldest first.
+pub static MIGRATIONS: &[Migration] = &[
+    Migration::new(1, "create_instruments", include_str!("../../../migrations/0001_create_instruments.sql")),
+    Migration::new(
+        2,
+        "create_instrument_snapshots",
+        include_str!("../../../migrations/0002_create_instrument_snapshots.sql"),
+    ),
+    Migration::new(3, "create_futures", include_str!("../../../migrations/0003_create_futures.sql")),
+    Migration::new(4, "create_historical_data", include_str!("../../../migrations/0004_create_historical_data.sql")),
+    Migration::new(5, "nullable_base_expiry", include_str!("../../../migrations/0005_nullable_base_expiry.sql")),
+];
+
+clickhouse_table! {
+    #[clickhouse_table(name = "schema_migrations", engine = "ReplacingMergeTree", order_by = "(version)")]
+    /// A row of the `schema_migrations` bookkeeping table.
+    #[derive(Debug, Clone, PartialEq, clickhouse::Row, serde::Serialize, serde::Deserialize)]
+    pub struct AppliedMigration {
+        pub version: u32,
+        pub name: String,
+        pub checksum: String,
+        #[serde(with = "clickhouse::serde::chrono::datetime")]
+        pub applied_at: DateTime<Utc>,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MigrationState {
+    Pending,
+    Applied { applied_at: DateTime<Utc> },
+    /// Applied, but the embedded SQL no longer matches the recorded checksum.
+    Modified { applied_checksum: String },
+    /// Recorded in `schema_migrations` but not embedded in this build, e.g. from a newer release.
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MigrationStatus {
+    pub version: u32,
+    pub name: String,
+    pub state: MigrationState,
+}
+
+/// Checks that versions are unique and ascending.
+pub fn validate_order(migrations: &[Migration]) -> Result<(), MigrationError> {
+    for pair in migrations.windows(2) {
+        if pair[1].version <= pair[0].version {
+            return Err(MigrationError::InvalidOrder(pair[1].version));
+        }
+    }
+    Ok(())
+}
+
+/// Compares embedded migrations with the rows of `schema_migrations`, ordered by version.
+pub fn migration_status(migrations: &[Migration], applied: &[AppliedMigration]) -> Vec<MigrationStatus> {
+    let applied_by_version: HashMap<u32, &AppliedMigration> = applied.iter().map(|a| (a.version, a)).collect();
+
+    let mut statuses: Vec<MigrationStatus> = migrations
+        .iter()
+        .map(|migration| {
+            let state = match applied_by_version.get(&migration.version) {
+                None => MigrationState::Pending,
+                Some(row) if row.checksum == migration.checksum() => MigrationState::Applied {
+                    applied_at: row.applied_at,
+                },
+                Some(row) => MigrationState::Modified {
+                    applied_checksum: row.checksum.clone(),
+                },
+            };
+            MigrationStatus {
+                version: migration.version,
+                name: migration.name.to_string(),
+                state,
+            }
+        })
+        .collect();
+
+    statuses.extend(
+        applied
+            .iter()
+            .filter(|row| !migrations.iter().any(|m| m.version == row.version))
+            .map(|row| MigrationStatus {
+                version: row.version,
+                name: row.name.clone(),
+                state: MigrationState::Unknown,
+            }),
+    );
+    statuses.sort_by_key(|s| s.version);
+    statuses
+}
+
+/// Migrations still to run, or an error if an applied one was edited.
+pub fn pending_migrations<'a>(
+    migrations: &'a [Migration],
+    applied: &[AppliedMigration],
+) -> Result<Vec<&'a Migration>, MigrationError> {
+    validate_order(migrations)?;
+
+    let statuses = migration_status(migrations, applied);
+    if let Some(modified) = statuses
+        .iter()
+        .find(|s| matches!(s.state, MigrationState::Modified { .. }))
+    {
+        return Err(MigrationError::ChecksumMismatch {
+            version: modified.version,
+            name: modified.name.clone(),
+        });
+    }
+
+    Ok(migrations
+        .iter()
+        .filter(|m| statuses.iter().any(|s| s.version == m.version && s.state == MigrationState::Pending))
+        .collect())
+}
@@ -0,0 +1,9 @@
+mod migration;
+mod runner;
+mod drift;
+mod error;
+
+pub use migration::*;
+pub use runner::*;
+pub use drift::*;
+pub use error::*;
@@ -0,0 +1,119 @@
+use crate::data_models::migrations::{
+    migration_status, pending_migrations, AppliedMigration, Migration, MigrationError, MigrationStatus, MIGRATIONS,
+};
+use crate::data_models::{ClickHouseTable, SchemaTarget};
+use chrono::Utc;
+use clickhouse::Client;
+
+/// Applies `Migration`s through a ClickHouse client, recording them in `schema_migrations`.
+pub struct MigrationRunner {
+    client: Client,
+    target: SchemaTarget,
+    migrations: Vec<Migration>,
+}
+
+impl MigrationRunner {
+    /// Runner for the migrations embedded in the crate, creating tables in `target`.
+    pub fn new(client: Client, target: SchemaTarget) -> Self {
+        Self::with_migrations(client, target, MIGRATIONS.to_vec())
+    }
+
+    pub fn with_migrations(client: Client, target: SchemaTarget, migrations: Vec<Migration>) -> Self {
+        Self {
+            client,
+            target,
+            migrations,
+        }
+    }
+
+    pub fn target(&self) -> &SchemaTarget {
+        &self.target
+    }
+
+    pub fn migrations(&self) -> &[Migration] {
+        &self.migrations
+    }
+
+    /// State of every known migration. Read-only: a missing `schema_migrations` means nothing is applied.
+    pub async fn status(&self) -> Result<Vec<MigrationStatus>, MigrationError> {
+        let applied = self.applied().await?;
+        Ok(migration_status(&self.migrations, &applied))
+    }
+
+    /// The migrations `apply` would run, in order, without running them or creating any table.
+    pub async fn dry_run(&self) -> Result<Vec<Migration>, MigrationError> {
+        let applied = self.applied().await?;
+        Ok(pending_migrations(&self.migrations, &applied)?
+            .into_iter()
+            .cloned()
+            .collect())
+    }
+
+    /// Runs pending migrations in version order and returns the versions applied.
+    /// Stops at the first failure; migrations before it stay recorded.
+    pub async fn apply(&self) -> Result<Vec<u32>, MigrationError> {
+        self.ensure_table().await?;
+        let applied = self.applied().await?;
+        let pending = pending_migrations(&self.migrations, &applied)?;
+
+        let mut versions = Vec::with_capacity(pending.len());
+        for migration in pending {
+            for statement in migration.statements(&self.target) {
+                self.client.query(&statement).execute().await?;
+            }
+            self.record(migration).await?;
+            versions.push(migration.version);
+        }
+        Ok(versions)
+    }
+
+    async fn ensure_table(&self) -> Result<(), MigrationError> {
+        self.client
+            .query(&AppliedMigration::create_table_in(&self.target))
+            .execute()
+            .await?;
+        Ok(())
+    }
+
+    async fn table_exists(&self) -> Result<bool, MigrationError> {
+        let count = self
+            .client
+            .query("SELECT count() FROM system.tables WHERE database = ? AND name = ?")
+            .bind(self.target.database())
+            .bind(self.target.table_name(AppliedMigration::TABLE))
+            .fetch_one::<u64>()
+            .await?;
+        Ok(count > 0)
+    }
+
+    async fn applied(&self) -> Result<Vec<AppliedMigration>, MigrationError> {
+        if !self.table_exists().await? {
+            return Ok(Vec::new());
+        }
+        let rows = self
+            .client
+            .query(&format!(
+                "SELECT ?fields FROM {} FINAL ORDER BY version",
+                self.target.table(AppliedMigration::TABLE)
+            ))
+            .fetch_all::<AppliedMigration>()
+            .await?;
+        Ok(rows)
+    }
+
+    async fn record(&self, migration: &Migration) -> Result<(), MigrationError> {
+        let mut insert = self
+            .client
+            .insert::<AppliedMigration>(&self.target.table(AppliedMigration::TABLE))?;
+        insert
+            .write(&AppliedMigration {
+                version: migration.version,
+                name: migration.name.to_string(),
+                checksum: migration.checksum(),
+                applied_at: Utc::now(),
+            })
+            .await?;
+        insert.end().await?;
+        Ok(())
+    }
+}
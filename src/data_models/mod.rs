@@ -3,3 +3,16 @@ pub mod instrument_data;
 pub mod hist_data;
 pub mod tick_data;
 pub mod futures_data;
+pub mod calendar_data;
+pub mod migrations;
+pub mod repository;
+pub mod kite_str;
+pub mod strike_ladder;
+mod kite_enum;
+mod clickhouse_schema;
+mod parse_enum_error;
+
+pub(crate) use kite_enum::kite_enum;
+pub(crate) use clickhouse_schema::{clickhouse_enum, clickhouse_table};
+pub use clickhouse_schema::{ClickHouseEnum, ClickHouseTable, ClickHouseType, SchemaTarget};
+pub use parse_enum_error::ParseEnumError;
@@ -0,0 +1,25 @@
+use std::fmt;
+
+/// Returned when a string or discriminant does not name a known enum variant.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseEnumError {
+    pub kind: &'static str,
+    pub value: String,
+}
+
+impl ParseEnumError {
+    pub fn new(kind: &'static str, value: impl ToString) -> Self {
+        Self {
+            kind,
+            value: value.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ParseEnumError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid {} value: {}", self.kind, self.value)
+    }
+}
+
+impl std::error::Error for ParseEnumError {}
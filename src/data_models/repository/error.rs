@@ -0,0 +1,40 @@
+use crate::data_models::futures_data::StagingIssue;
+use std::fmt;
+
+/// **Repository Errors**
+#[derive(Debug)]
+pub enum RepoError {
+    ClickHouse(clickhouse::error::Error),
+    /// Staging failed validation; nothing was swapped.
+    StagingRejected(Vec<StagingIssue>),
+    /// The swap went through but the result was wrong, so it was swapped back.
+    RolledBack(String),
+}
+
+impl fmt::Display for RepoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RepoError::ClickHouse(e) => write!(f, "ClickHouse error: {}", e),
+            RepoError::StagingRejected(issues) => {
+                let issues: Vec<String> = issues.iter().map(ToString::to_string).collect();
+                write!(f, "Staging rejected: {}", issues.join("; "))
+            }
+            RepoError::RolledBack(reason) => write!(f, "Promotion rolled back: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for RepoError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RepoError::ClickHouse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<clickhouse::error::Error> for RepoError {
+    fn from(e: clickhouse::error::Error) -> Self {
+        RepoError::ClickHouse(e)
+    }
+}
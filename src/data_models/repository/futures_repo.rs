@@ -0,0 +1,220 @@
+use crate::data_models::futures_data::queries::populate_future_staging;
+use crate::data_models::futures_data::{FuturesData, FuturesDiff, PromotionRules};
+use crate::data_models::instrument_data::BaseExchange;
+use crate::data_models::repository::{RepoError, TableRepo};
+use crate::data_models::{ClickHouseEnum, SchemaTarget};
+use chrono::NaiveDate;
+use clickhouse::Client;
+use std::cmp::Ordering;
+
+/// How `promote_staging` swaps `futures_staging` into `futures`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwapMethod {
+    /// `EXCHANGE TABLES`, atomic. Needs an `Atomic` database, the default since ClickHouse 20.10.
+    Exchange,
+    /// Three renames in one `RENAME TABLE`, for `Ordinary` databases.
+    Rename,
+}
+
+/// Outcome of a successful `promote_staging`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuturesPromotion {
+    pub previous_rows: usize,
+    pub promoted_rows: usize,
+    /// `futures` before the swap against after it.
+    pub diff: FuturesDiff,
+}
+
+/// The `futures` table and the `futures_staging` table it is rebuilt from.
+#[derive(Clone)]
+pub struct FuturesRepo {
+    target: SchemaTarget,
+    futures: TableRepo<FuturesData>,
+    staging: TableRepo<FuturesData>,
+    rules: PromotionRules,
+    swap: SwapMethod,
+}
+
+impl FuturesRepo {
+    pub fn new(client: Client, target: SchemaTarget) -> Self {
+        Self {
+            futures: TableRepo::new(client.clone(), &target),
+            staging: TableRepo::named(client, &target, "futures_staging"),
+            target,
+            rules: PromotionRules::default(),
+            swap: SwapMethod::Exchange,
+        }
+    }
+
+    pub fn with_rules(mut self, rules: PromotionRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    pub fn with_swap(mut self, swap: SwapMethod) -> Self {
+        self.swap = swap;
+        self
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.futures = self.futures.with_batch_size(batch_size);
+        self.staging = self.staging.with_batch_size(batch_size);
+        self
+    }
+
+    pub fn futures(&self) -> &TableRepo<FuturesData> {
+        &self.futures
+    }
+
+    pub fn staging(&self) -> &TableRepo<FuturesData> {
+        &self.staging
+    }
+
+    pub async fn insert(&self, futures: &[FuturesData]) -> Result<usize, RepoError> {
+        self.futures.insert(futures).await
+    }
+
+    pub async fn replace_all(&self, futures: &[FuturesData]) -> Result<usize, RepoError> {
+        self.futures.replace(futures).await
+    }
+
+    pub async fn truncate(&self) -> Result<(), RepoError> {
+        self.futures.truncate().await
+    }
+
+    pub async fn fetch_all(&self) -> Result<Vec<FuturesData>, RepoError> {
+        self.futures.fetch_all().await
+    }
+
+    pub async fn get(
+        &self,
+        base_exchange: BaseExchange,
+        name: &str,
+        expiry: NaiveDate,
+    ) -> Result<Option<FuturesData>, RepoError> {
+        let future = self
+            .futures
+            .select("WHERE base_exchange = ? AND name = ? AND expiry = ? LIMIT 1")
+            .bind(base_exchange.clickhouse_name())
+            .bind(name)
+            .bind(expiry)
+            .fetch_optional::<FuturesData>()
+            .await?;
+        Ok(future)
+    }
+
+    /// Every expiry of one underlying, nearest first.
+    pub async fn by_name(&self, base_exchange: BaseExchange, name: &str) -> Result<Vec<FuturesData>, RepoError> {
+        let futures = self
+            .futures
+            .select("WHERE base_exchange = ? AND name = ? ORDER BY expiry")
+            .bind(base_exchange.clickhouse_name())
+            .bind(name)
+            .fetch_all::<FuturesData>()
+            .await?;
+        Ok(futures)
+    }
+
+    /// Futures expiring between `from` and `to`, inclusive.
+    pub async fn expiring_between(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<FuturesData>, RepoError> {
+        let futures = self
+            .futures
+            .select("WHERE expiry >= ? AND expiry <= ? ORDER BY base_exchange, name, expiry")
+            .bind(from)
+            .bind(to)
+            .fetch_all::<FuturesData>()
+            .await?;
+        Ok(futures)
+    }
+
+    /// Empties `futures_staging` and refills it from `instruments`, keeping `add_to_base` from `futures`.
+    pub async fn rebuild_staging(&self) -> Result<(), RepoError> {
+        self.staging.truncate().await?;
+        self.staging
+            .client()
+            .query(&populate_future_staging(&self.target))
+            .execute()
+            .await?;
+        Ok(())
+    }
+
+    /// Validates `futures_staging` against `rules` and swaps it with `futures`, leaving the
+    /// previous contents in `futures_staging`. If `futures` doesn't hold exactly the validated
+    /// rows after the swap, e.g. because staging was written to meanwhile, the tables are
+    /// swapped back. Don't write to `futures_staging` while a promotion runs.
+    pub async fn promote_staging(&self) -> Result<FuturesPromotion, RepoError> {
+        let staged = self.staging.fetch_all().await?;
+        let live = self.futures.fetch_all().await?;
+
+        let issues = self.rules.validate(&staged, &live);
+        if !issues.is_empty() {
+            return Err(RepoError::StagingRejected(issues));
+        }
+
+        self.swap_tables().await?;
+        let failure = match self.futures.fetch_all().await {
+            Ok(promoted) if same_rows(&promoted, &staged) => None,
+            Ok(promoted) if promoted.len() != staged.len() => Some(format!(
+                "futures has {} rows after the swap, expected {}",
+                promoted.len(),
+                staged.len()
+            )),
+            Ok(_) => Some("futures differs from the validated staging rows after the swap".to_string()),
+            Err(e) => Some(e.to_string()),
+        };
+        if let Some(reason) = failure {
+            self.swap_tables().await?;
+            return Err(RepoError::RolledBack(reason));
+        }
+
+        Ok(FuturesPromotion {
+            previous_rows: live.len(),
+            promoted_rows: staged.len(),
+            diff: FuturesDiff::between(&live, &staged),
+        })
+    }
+
+    /// Swaps `futures` and `futures_staging`. Swapping again undoes it.
+    async fn swap_tables(&self) -> Result<(), RepoError> {
+        let (futures, staging) = (self.futures.table(), self.staging.table());
+        let sql = match self.swap {
+            SwapMethod::Exchange => format!("EXCHANGE TABLES {} AND {}", staging, futures),
+            SwapMethod::Rename => {
+                let swap = self.target.table("futures_swap");
+                format!("RENAME TABLE {0} TO {2}, {1} TO {0}, {2} TO {1}", futures, staging, swap)
+            }
+        };
+        self.futures.client().query(&sql).execute().await?;
+        Ok(())
+    }
+}
+
+/// Whether both hold the same rows, in any order.
+fn same_rows(a: &[FuturesData], b: &[FuturesData]) -> bool {
+    let sorted = |rows: &[FuturesData]| {
+        let mut rows = rows.to_vec();
+        rows.sort_by(row_order);
+        rows
+    };
+    a.len() == b.len() && sorted(a) == sorted(b)
+}
+
+/// A total order over every column, so rows that share a key still sort the same way.
+fn row_order(x: &FuturesData, y: &FuturesData) -> Ordering {
+    type Columns<'a> = (i8, &'a str, NaiveDate, u16, i8, u64, Option<NaiveDate>);
+    fn columns(f: &FuturesData) -> Columns<'_> {
+        let future_type = f.future_type.discriminant();
+        (f.base_exchange as i8, &f.name, f.expiry, f.dte, future_type, f.underlying, f.base_expiry)
+    }
+    columns(x)
+        .cmp(&columns(y))
+        .then_with(|| x.add_to_base.total_cmp(&y.add_to_base))
+        .then_with(|| {
+            x.strike
+                .iter()
+                .zip(&y.strike)
+                .map(|(a, b)| a.total_cmp(b))
+                .find(|o| o.is_ne())
+                .unwrap_or_else(|| x.strike.len().cmp(&y.strike.len()))
+        })
+}
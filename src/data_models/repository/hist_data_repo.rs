@@ -0,0 +1,113 @@
+use crate::data_models::hist_data::{HistData, HistDataMaxDatetime, Interval};
+use crate::data_models::repository::{RepoError, TableRepo};
+use crate::data_models::{ClickHouseEnum, SchemaTarget};
+use chrono::{DateTime, Utc};
+use clickhouse::Client;
+
+/// Candles in `historical_data`, and the latest candle per instrument in `historical_data_max_datetime`.
+#[derive(Clone)]
+pub struct HistDataRepo {
+    candles: TableRepo<HistData>,
+    max_datetimes: TableRepo<HistDataMaxDatetime>,
+}
+
+impl HistDataRepo {
+    pub fn new(client: Client, target: SchemaTarget) -> Self {
+        Self {
+            candles: TableRepo::new(client.clone(), &target),
+            max_datetimes: TableRepo::new(client, &target),
+        }
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.candles = self.candles.with_batch_size(batch_size);
+        self.max_datetimes = self.max_datetimes.with_batch_size(batch_size);
+        self
+    }
+
+    pub fn candles(&self) -> &TableRepo<HistData> {
+        &self.candles
+    }
+
+    pub fn max_datetimes(&self) -> &TableRepo<HistDataMaxDatetime> {
+        &self.max_datetimes
+    }
+
+    /// Candles already stored for the same symbol, interval and time are replaced on merge.
+    pub async fn insert(&self, candles: &[HistData]) -> Result<usize, RepoError> {
+        self.candles.insert(candles).await
+    }
+
+    pub async fn truncate(&self) -> Result<(), RepoError> {
+        self.candles.truncate().await?;
+        self.max_datetimes.truncate().await
+    }
+
+    /// Candles from `from` up to but excluding `to`, oldest first.
+    pub async fn range(
+        &self,
+        tradingsymbol: &str,
+        interval: Interval,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<HistData>, RepoError> {
+        let candles = self
+            .candles
+            .select(
+                "FINAL WHERE tradingsymbol = ? AND interval = ? \
+                 AND datetime >= toDateTime(?) AND datetime < toDateTime(?) ORDER BY datetime",
+            )
+            .bind(tradingsymbol)
+            .bind(interval.clickhouse_name())
+            .bind(from.timestamp())
+            .bind(to.timestamp())
+            .fetch_all::<HistData>()
+            .await?;
+        Ok(candles)
+    }
+
+    /// Deletes candles from `from` up to but excluding `to`, e.g. before re-fetching a bad range.
+    pub async fn delete_range(
+        &self,
+        tradingsymbol: &str,
+        interval: Interval,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<(), RepoError> {
+        self.candles
+            .client()
+            .query(&format!(
+                "ALTER TABLE {} DELETE WHERE tradingsymbol = ? AND interval = ? \
+                 AND datetime >= toDateTime(?) AND datetime < toDateTime(?)",
+                self.candles.table()
+            ))
+            .bind(tradingsymbol)
+            .bind(interval.clickhouse_name())
+            .bind(from.timestamp())
+            .bind(to.timestamp())
+            .execute()
+            .await?;
+        Ok(())
+    }
+
+    /// Where a backfill for the instrument should resume, if anything is stored.
+    pub async fn max_datetime(
+        &self,
+        instrument_token: &str,
+        interval: Interval,
+    ) -> Result<Option<HistDataMaxDatetime>, RepoError> {
+        let row = self
+            .max_datetimes
+            .select("FINAL WHERE instrument_token = ? AND interval = ? LIMIT 1")
+            .bind(instrument_token)
+            .bind(interval.clickhouse_name())
+            .fetch_optional::<HistDataMaxDatetime>()
+            .await?;
+        Ok(row)
+    }
+
+    /// Records the latest stored candles; older rows for the same instrument and interval are replaced on merge.
+    pub async fn set_max_datetimes(&self, rows: &[HistDataMaxDatetime]) -> Result<usize, RepoError> {
+        self.max_datetimes.insert(rows).await
+    }
+}
@@ -0,0 +1,150 @@
+use crate::data_models::instrument_data::queries::{
+    delete_snapshot, select_instrument_history, select_instruments_as_of,
+};
+use crate::data_models::instrument_data::{Exchange, Instrument, InstrumentMaster, InstrumentSnapshot};
+use crate::data_models::repository::{RepoError, TableRepo};
+use crate::data_models::{ClickHouseEnum, SchemaTarget};
+use chrono::NaiveDate;
+use clickhouse::Client;
+
+/// The `instruments` table and its daily `instrument_snapshots`.
+#[derive(Clone)]
+pub struct InstrumentRepo {
+    target: SchemaTarget,
+    instruments: TableRepo<Instrument>,
+    snapshots: TableRepo<InstrumentSnapshot>,
+}
+
+impl InstrumentRepo {
+    pub fn new(client: Client, target: SchemaTarget) -> Self {
+        Self {
+            instruments: TableRepo::new(client.clone(), &target),
+            snapshots: TableRepo::new(client, &target),
+            target,
+        }
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.instruments = self.instruments.with_batch_size(batch_size);
+        self.snapshots = self.snapshots.with_batch_size(batch_size);
+        self
+    }
+
+    pub fn instruments(&self) -> &TableRepo<Instrument> {
+        &self.instruments
+    }
+
+    pub fn snapshots(&self) -> &TableRepo<InstrumentSnapshot> {
+        &self.snapshots
+    }
+
+    pub async fn insert(&self, instruments: &[Instrument]) -> Result<usize, RepoError> {
+        self.instruments.insert(instruments).await
+    }
+
+    /// Replaces the whole table with today's dump.
+    pub async fn replace_all(&self, instruments: &[Instrument]) -> Result<usize, RepoError> {
+        self.instruments.replace(instruments).await
+    }
+
+    pub async fn truncate(&self) -> Result<(), RepoError> {
+        self.instruments.truncate().await
+    }
+
+    pub async fn fetch_all(&self) -> Result<Vec<Instrument>, RepoError> {
+        self.instruments.fetch_all().await
+    }
+
+    /// Every stored instrument, indexed for lookups.
+    pub async fn load_master(&self) -> Result<InstrumentMaster, RepoError> {
+        Ok(InstrumentMaster::new(self.fetch_all().await?))
+    }
+
+    pub async fn by_token(&self, instrument_token: &str) -> Result<Option<Instrument>, RepoError> {
+        let instrument = self
+            .instruments
+            .select("WHERE instrument_token = ? LIMIT 1")
+            .bind(instrument_token)
+            .fetch_optional::<Instrument>()
+            .await?;
+        Ok(instrument)
+    }
+
+    pub async fn by_symbol(&self, exchange: Exchange, tradingsymbol: &str) -> Result<Option<Instrument>, RepoError> {
+        let instrument = self
+            .instruments
+            .select("WHERE exchange = ? AND tradingsymbol = ? LIMIT 1")
+            .bind(exchange.clickhouse_name())
+            .bind(tradingsymbol)
+            .fetch_optional::<Instrument>()
+            .await?;
+        Ok(instrument)
+    }
+
+    /// Every contract on the underlying `name`, ordered by expiry then strike.
+    pub async fn by_name(&self, name: &str) -> Result<Vec<Instrument>, RepoError> {
+        let instruments = self
+            .instruments
+            .select("WHERE name = ? ORDER BY expiry, strike")
+            .bind(name)
+            .fetch_all::<Instrument>()
+            .await?;
+        Ok(instruments)
+    }
+
+    /// Contracts expiring between `from` and `to`, inclusive.
+    pub async fn expiring_between(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<Instrument>, RepoError> {
+        let instruments = self
+            .instruments
+            .select("WHERE expiry >= ? AND expiry <= ? ORDER BY expiry, name, strike")
+            .bind(from)
+            .bind(to)
+            .fetch_all::<Instrument>()
+            .await?;
+        Ok(instruments)
+    }
+
+    /// Stores `instruments` as the snapshot for `date`. Rows re-inserted for a date replace the old ones on merge.
+    pub async fn insert_snapshot(&self, date: NaiveDate, instruments: &[Instrument]) -> Result<usize, RepoError> {
+        let rows: Vec<InstrumentSnapshot> = instruments
+            .iter()
+            .cloned()
+            .map(|instrument| InstrumentSnapshot::new(date, instrument))
+            .collect();
+        self.snapshots.insert(&rows).await
+    }
+
+    /// Instruments as listed on `date`: the latest snapshot on or before it.
+    pub async fn as_of(&self, date: NaiveDate) -> Result<Vec<InstrumentSnapshot>, RepoError> {
+        let rows = self
+            .snapshots
+            .client()
+            .query(&select_instruments_as_of(&self.target))
+            .bind(date)
+            .fetch_all::<InstrumentSnapshot>()
+            .await?;
+        Ok(rows)
+    }
+
+    /// Every stored version of an instrument, oldest first.
+    pub async fn history(&self, instrument_token: &str) -> Result<Vec<InstrumentSnapshot>, RepoError> {
+        let rows = self
+            .snapshots
+            .client()
+            .query(&select_instrument_history(&self.target))
+            .bind(instrument_token)
+            .fetch_all::<InstrumentSnapshot>()
+            .await?;
+        Ok(rows)
+    }
+
+    pub async fn delete_snapshot(&self, date: NaiveDate) -> Result<(), RepoError> {
+        self.snapshots
+            .client()
+            .query(&delete_snapshot(&self.target))
+            .bind(date)
+            .execute()
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,11 @@
+mod error;
+mod table_repo;
+mod instrument_repo;
+mod futures_repo;
+mod hist_data_repo;
+
+pub use error::*;
+pub use table_repo::*;
+pub use instrument_repo::*;
+pub use futures_repo::*;
+pub use hist_data_repo::*;
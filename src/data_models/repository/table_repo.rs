@@ -0,0 +1,116 @@
+use crate::data_models::repository::RepoError;
+use crate::data_models::{ClickHouseTable, SchemaTarget};
+use clickhouse::query::Query;
+use clickhouse::{Client, Row};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::marker::PhantomData;
+
+/// Rows sent per `INSERT` unless changed with `with_batch_size`.
+pub const DEFAULT_BATCH_SIZE: usize = 10_000;
+
+/// Typed access to the table of row type `T`. The specific repositories wrap one of these;
+/// use it directly for tables without one.
+pub struct TableRepo<T> {
+    client: Client,
+    table: String,
+    batch_size: usize,
+    _row: PhantomData<T>,
+}
+
+// Derived `Clone` would require `T: Clone`.
+impl<T> Clone for TableRepo<T> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            table: self.table.clone(),
+            batch_size: self.batch_size,
+            _row: PhantomData,
+        }
+    }
+}
+
+impl<T> TableRepo<T>
+where
+    T: ClickHouseTable + Row + Serialize + DeserializeOwned,
+{
+    pub fn new(client: Client, target: &SchemaTarget) -> Self {
+        Self::named(client, target, T::TABLE)
+    }
+
+    /// `T`'s rows in another table of the same shape, e.g. `futures_staging`.
+    pub fn named(client: Client, target: &SchemaTarget, table: &str) -> Self {
+        Self {
+            client,
+            table: target.table(table),
+            batch_size: DEFAULT_BATCH_SIZE,
+            _row: PhantomData,
+        }
+    }
+
+    /// Caps the rows per `INSERT`. Zero is treated as one.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Fully qualified table name.
+    pub fn table(&self) -> &str {
+        &self.table
+    }
+
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    /// Inserts `rows` in batches of `batch_size` and returns how many were written.
+    /// Batches before a failing one stay inserted.
+    pub async fn insert(&self, rows: &[T]) -> Result<usize, RepoError> {
+        for batch in rows.chunks(self.batch_size) {
+            let mut insert = self.client.insert::<T>(&self.table)?;
+            for row in batch {
+                insert.write(row).await?;
+            }
+            insert.end().await?;
+        }
+        Ok(rows.len())
+    }
+
+    pub async fn truncate(&self) -> Result<(), RepoError> {
+        self.client
+            .query(&format!("TRUNCATE TABLE {}", self.table))
+            .execute()
+            .await?;
+        Ok(())
+    }
+
+    /// Empties the table and inserts `rows`. Not atomic: readers may briefly see an empty table.
+    pub async fn replace(&self, rows: &[T]) -> Result<usize, RepoError> {
+        self.truncate().await?;
+        self.insert(rows).await
+    }
+
+    /// `SELECT ?fields FROM <table> <clause>`, e.g. `WHERE name = ? ORDER BY expiry`.
+    /// Bind the clause's `?` parameters on the returned query.
+    pub(crate) fn select(&self, clause: &str) -> Query {
+        self.client
+            .query(&format!("SELECT ?fields FROM {} {}", self.table, clause))
+    }
+
+    pub async fn fetch_all(&self) -> Result<Vec<T>, RepoError> {
+        Ok(self.select("").fetch_all::<T>().await?)
+    }
+
+    pub async fn count(&self) -> Result<u64, RepoError> {
+        let count = self
+            .client
+            .query(&format!("SELECT count() FROM {}", self.table))
+            .fetch_one::<u64>()
+            .await?;
+        Ok(count)
+    }
+}
@@ -0,0 +1,248 @@
+use std::cmp::Ordering;
+use std::ops::Range;
+
+/// Gaps closer than this are the same step, so `0.1 + 0.2` style noise doesn't split a grid.
+const STEP_EPSILON: f64 = 1e-6;
+
+/// Which neighbour `nearest` picks when `spot` is exactly between two strikes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    Lower,
+    Higher,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionSide {
+    Call,
+    Put,
+}
+
+/// Where a strike sits relative to spot, for one side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Moneyness {
+    DeepItm,
+    Itm,
+    Atm,
+    Otm,
+    DeepOtm,
+}
+
+/// A run of strikes with the same spacing, e.g. 50-point strikes near the money.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StepSegment {
+    pub from: f64,
+    pub to: f64,
+    pub step: f64,
+}
+
+/// Sorted, de-duplicated strikes of one underlying and expiry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrikeLadder {
+    strikes: Vec<f64>,
+}
+
+impl StrikeLadder {
+    pub fn new(mut strikes: Vec<f64>) -> Self {
+        strikes.retain(|s| s.is_finite());
+        strikes.sort_by(f64::total_cmp);
+        strikes.dedup();
+        Self { strikes }
+    }
+
+    /// Lowest first.
+    pub fn strikes(&self) -> &[f64] {
+        &self.strikes
+    }
+
+    pub fn len(&self) -> usize {
+        self.strikes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strikes.is_empty()
+    }
+
+    pub fn contains(&self, strike: f64) -> bool {
+        self.index_of(strike).is_some()
+    }
+
+    /// The strike closest to `spot`.
+    pub fn nearest(&self, spot: f64, tie: TieBreak) -> Option<f64> {
+        self.nearest_index(spot, tie).map(|i| self.strikes[i])
+    }
+
+    /// The nearest strike, ties going to the lower one. `OptionChain::atm_strike` uses this.
+    pub fn atm(&self, spot: f64) -> Option<f64> {
+        self.nearest(spot, TieBreak::Lower)
+    }
+
+    /// The `k` strikes closest to `spot`, lowest first. Equidistant strikes prefer the lower one.
+    pub fn nearest_k(&self, spot: f64, k: usize) -> &[f64] {
+        let Some(atm) = self.nearest_index(spot, TieBreak::Lower) else {
+            return &[];
+        };
+        if k == 0 {
+            return &[];
+        }
+
+        // Grow the window [lo, hi) around the ATM strike one closer neighbour at a time.
+        let (mut lo, mut hi) = (atm, atm + 1);
+        while hi - lo < k.min(self.strikes.len()) {
+            let lower = match (lo.checked_sub(1), self.strikes.get(hi)) {
+                (Some(below), Some(above)) => (spot - self.strikes[below]).abs() <= (above - spot).abs(),
+                (Some(_), None) => true,
+                (None, _) => false,
+            };
+            if lower {
+                lo -= 1;
+            } else {
+                hi += 1;
+            }
+        }
+        &self.strikes[lo..hi]
+    }
+
+    /// The ATM strike and up to `n` strikes either side of it.
+    pub fn around_atm(&self, spot: f64, n: usize) -> &[f64] {
+        &self.strikes[self.around_atm_range(spot, n)]
+    }
+
+    /// The most common gap between adjacent strikes; the smaller one if two are equally common.
+    pub fn step(&self) -> Option<f64> {
+        let mut counts: Vec<(f64, usize)> = Vec::new();
+        for gap in self.gaps() {
+            match counts.iter_mut().find(|(step, _)| same_step(*step, gap)) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((gap, 1)),
+            }
+        }
+        counts
+            .into_iter()
+            .max_by(|a, b| a.1.cmp(&b.1).then(b.0.total_cmp(&a.0)))
+            .map(|(step, _)| step)
+    }
+
+    /// Whether every adjacent pair is one `step()` apart.
+    pub fn is_uniform(&self) -> bool {
+        self.step_segments().len() <= 1
+    }
+
+    /// Runs of equal spacing, lowest first. A uniform ladder is one segment.
+    pub fn step_segments(&self) -> Vec<StepSegment> {
+        let mut segments: Vec<StepSegment> = Vec::new();
+        for pair in self.strikes.windows(2) {
+            let gap = pair[1] - pair[0];
+            match segments.last_mut() {
+                Some(segment) if same_step(segment.step, gap) => segment.to = pair[1],
+                _ => segments.push(StepSegment {
+                    from: pair[0],
+                    to: pair[1],
+                    step: gap,
+                }),
+            }
+        }
+        segments
+    }
+
+    /// The spacing around `strike`: the gap to the next strike up, or down for the highest.
+    pub fn step_at(&self, strike: f64) -> Option<f64> {
+        let i = self.index_of(strike)?;
+        match (self.strikes.get(i + 1), i.checked_sub(1)) {
+            (Some(next), _) => Some(next - strike),
+            (None, Some(below)) => Some(strike - self.strikes[below]),
+            (None, None) => None,
+        }
+    }
+
+    /// In-the-money strikes for `side`, lowest first. A strike equal to spot is neither ITM nor OTM.
+    pub fn itm(&self, spot: f64, side: OptionSide) -> &[f64] {
+        let (below, above) = self.split(spot);
+        match side {
+            OptionSide::Call => below,
+            OptionSide::Put => above,
+        }
+    }
+
+    /// Out-of-the-money strikes for `side`, lowest first.
+    pub fn otm(&self, spot: f64, side: OptionSide) -> &[f64] {
+        let (below, above) = self.split(spot);
+        match side {
+            OptionSide::Call => above,
+            OptionSide::Put => below,
+        }
+    }
+
+    /// `strike`'s bucket for `side`, counting ladder steps from the ATM strike. Strikes
+    /// `deep_after` or more steps away are deep. `None` if `strike` isn't listed.
+    pub fn moneyness(&self, strike: f64, spot: f64, side: OptionSide, deep_after: usize) -> Option<Moneyness> {
+        let i = self.index_of(strike)?;
+        let atm = self.nearest_index(spot, TieBreak::Lower)?;
+        if i == atm {
+            return Some(Moneyness::Atm);
+        }
+
+        let steps = i.abs_diff(atm);
+        let itm = match side {
+            OptionSide::Call => strike < spot,
+            OptionSide::Put => strike > spot,
+        };
+        Some(match (itm, steps >= deep_after) {
+            (true, true) => Moneyness::DeepItm,
+            (true, false) => Moneyness::Itm,
+            (false, true) => Moneyness::DeepOtm,
+            (false, false) => Moneyness::Otm,
+        })
+    }
+
+    /// Every strike with its bucket, lowest first.
+    pub fn buckets(&self, spot: f64, side: OptionSide, deep_after: usize) -> Vec<(f64, Moneyness)> {
+        self.strikes
+            .iter()
+            .filter_map(|&strike| Some((strike, self.moneyness(strike, spot, side, deep_after)?)))
+            .collect()
+    }
+
+    fn gaps(&self) -> impl Iterator<Item = f64> + '_ {
+        self.strikes.windows(2).map(|pair| pair[1] - pair[0])
+    }
+
+    /// Position of `strike` in `strikes()`.
+    pub(crate) fn index_of(&self, strike: f64) -> Option<usize> {
+        self.strikes.binary_search_by(|s| s.total_cmp(&strike)).ok()
+    }
+
+    /// Strikes below `spot` and strikes above it.
+    fn split(&self, spot: f64) -> (&[f64], &[f64]) {
+        let below = self.strikes.partition_point(|s| *s < spot);
+        let above = self.strikes.partition_point(|s| *s <= spot);
+        (&self.strikes[..below], &self.strikes[above..])
+    }
+
+    /// Indexes of `around_atm`; empty for an empty ladder.
+    pub(crate) fn around_atm_range(&self, spot: f64, n: usize) -> Range<usize> {
+        match self.nearest_index(spot, TieBreak::Lower) {
+            Some(atm) => atm.saturating_sub(n)..(atm + n + 1).min(self.strikes.len()),
+            None => 0..0,
+        }
+    }
+
+    fn nearest_index(&self, spot: f64, tie: TieBreak) -> Option<usize> {
+        let above = self.strikes.partition_point(|s| *s < spot);
+        match (above.checked_sub(1), self.strikes.get(above)) {
+            (Some(below), Some(next)) => {
+                let order = (next - spot).total_cmp(&(spot - self.strikes[below]));
+                match (order, tie) {
+                    (Ordering::Less, _) | (Ordering::Equal, TieBreak::Higher) => Some(above),
+                    _ => Some(below),
+                }
+            }
+            (Some(below), None) => Some(below),
+            (None, Some(_)) => Some(above),
+            (None, None) => None,
+        }
+    }
+}
+
+fn same_step(a: f64, b: f64) -> bool {
+    (a - b).abs() < STEP_EPSILON
+}
@@ -0,0 +1,92 @@
+use clickhouse::Row;
+use project_models::data_models::futures_data::queries::{create_futures_staging_table, create_futures_table};
+use project_models::data_models::futures_data::{FutureType, FuturesData};
+use project_models::data_models::hist_data::queries::{create_hist_data_table, create_max_datetime_hist_data_table};
+use project_models::data_models::hist_data::{HistData, HistDataMaxDatetime, Interval};
+use project_models::data_models::instrument_data::queries::create_instruments_table;
+use project_models::data_models::instrument_data::{
+    BaseExchange, Exchange, Instrument, InstrumentSnapshot, InstrumentType, Segment,
+};
+use project_models::data_models::{ClickHouseEnum, ClickHouseTable, ClickHouseType, SchemaTarget};
+
+/// Each enum's `Enum8` values must be what `serde_repr` writes for it.
+fn assert_enum8_matches_repr<T: ClickHouseEnum + serde::Serialize>() {
+    for variant in T::variants() {
+        assert_eq!(serde_json::to_value(&variant).unwrap(), serde_json::json!(variant.discriminant()));
+    }
+}
+
+#[test]
+fn test_enum8_literals() {
+    assert_eq!(
+        BaseExchange::clickhouse_type(),
+        "Enum8('Bse' = 0, 'Mcx' = 1, 'Nse' = 2, 'Nseix' = 3, 'Global' = 4)"
+    );
+    assert_eq!(
+        FutureType::clickhouse_type(),
+        "Enum8('atm' = 1, 'add_to_base' = 2, 'liquid' = 3)"
+    );
+    assert_eq!(Segment::NfoOpt.clickhouse_name(), "NfoOpt");
+    assert_eq!(Interval::variants().len(), 8);
+
+    assert_enum8_matches_repr::<Exchange>();
+    assert_enum8_matches_repr::<Segment>();
+    assert_enum8_matches_repr::<InstrumentType>();
+    assert_enum8_matches_repr::<BaseExchange>();
+    assert_enum8_matches_repr::<FutureType>();
+    assert_enum8_matches_repr::<Interval>();
+}
+
+#[test]
+fn test_field_types() {
+    assert_eq!(<Option<chrono::NaiveDate>>::clickhouse_type(), "Nullable(Date)");
+    assert_eq!(<Vec<f64>>::clickhouse_type(), "Array(Float64)");
+
+    let columns = FuturesData::columns();
+    assert!(columns.contains(&("base_expiry", "Nullable(Date)".to_string())));
+    assert!(columns.contains(&("add_to_base", "Float64".to_string())));
+    assert!(HistData::columns().contains(&("datetime", "DateTime('Asia/Kolkata')".to_string())));
+}
+
+#[test]
+fn test_columns_follow_row_fields() {
+    fn names<T: ClickHouseTable>() -> Vec<&'static str> {
+        T::columns().into_iter().map(|(name, _)| name).collect()
+    }
+    assert_eq!(names::<Instrument>(), Instrument::COLUMN_NAMES);
+    assert_eq!(names::<InstrumentSnapshot>(), InstrumentSnapshot::COLUMN_NAMES);
+    assert_eq!(names::<FuturesData>(), FuturesData::COLUMN_NAMES);
+    assert_eq!(names::<HistData>(), HistData::COLUMN_NAMES);
+    assert_eq!(names::<HistDataMaxDatetime>(), HistDataMaxDatetime::COLUMN_NAMES);
+}
+
+#[test]
+fn test_generated_create_table() {
+    let target = SchemaTarget::new("market");
+    assert_eq!(
+        create_hist_data_table(&target),
+        "CREATE TABLE IF NOT EXISTS market.historical_data
+(
+    tradingsymbol String,
+    open Float64,
+    high Float64,
+    low Float64,
+    close Float64,
+    volume UInt64,
+    datetime DateTime('Asia/Kolkata'),
+    interval Enum8('Day' = 0, 'Minute' = 1, 'ThreeMinute' = 2, 'FiveMinute' = 3, 'TenMinute' = 4, 'FifteenMinute' = 5, 'ThirtyMinute' = 6, 'SixtyMinute' = 7)
+)
+ENGINE = ReplacingMergeTree
+PARTITION BY (tradingsymbol, interval)
+ORDER BY (tradingsymbol, interval, datetime)"
+    );
+
+    let instruments = create_instruments_table(&target);
+    assert!(instruments.starts_with("CREATE TABLE IF NOT EXISTS market.instruments\n"));
+    assert!(instruments.ends_with("ENGINE = MergeTree()\nORDER BY (instrument_token)"));
+    assert!(create_max_datetime_hist_data_table(&target).contains("ORDER BY (instrument_token, interval)"));
+
+    let staging = create_futures_staging_table(&target);
+    assert!(staging.starts_with("CREATE TABLE IF NOT EXISTS market.futures_staging\n"));
+    assert_eq!(staging.replace("futures_staging", "futures"), create_futures_table(&target));
+}
@@ -0,0 +1,72 @@
+//! Fixtures and live ClickHouse setup shared by the integration tests.
+// Each test binary compiles its own copy and uses only some of it.
+#![allow(dead_code)]
+
+use chrono::NaiveDate;
+use project_models::data_models::futures_data::{FutureType, FuturesData};
+use project_models::data_models::instrument_data::BaseExchange;
+use project_models::data_models::migrations::MigrationRunner;
+use project_models::data_models::SchemaTarget;
+
+pub fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+/// A liquid NSE future with ten days to expiry and no base expiry.
+pub fn future(name: &str, expiry: NaiveDate, strike: Vec<f64>) -> FuturesData {
+    FuturesData {
+        base_exchange: BaseExchange::Nse,
+        name: name.to_string(),
+        expiry,
+        dte: 10,
+        future_type: FutureType::Liquid,
+        underlying: 256265,
+        base_expiry: None,
+        add_to_base: 0.0,
+        strike,
+    }
+}
+
+/// A throwaway database on the server at `CLICKHOUSE_URL` (default `http://localhost:8123`),
+/// so live tests never touch real tables. The database is dropped with the guard, even when
+/// the test panics.
+pub struct TestDatabase {
+    pub client: clickhouse::Client,
+    pub target: SchemaTarget,
+    url: String,
+}
+
+impl TestDatabase {
+    /// Creates `<name>_<pid>` without applying any migrations.
+    pub async fn empty(name: &str) -> Self {
+        let url = std::env::var("CLICKHOUSE_URL").unwrap_or_else(|_| "http://localhost:8123".to_string());
+        let client = clickhouse::Client::default().with_url(&url);
+        let target = SchemaTarget::new(format!("{}_{}", name, std::process::id()));
+        client
+            .query(&format!("CREATE DATABASE IF NOT EXISTS {}", target.database()))
+            .execute()
+            .await
+            .unwrap();
+        Self { client, target, url }
+    }
+
+    /// Creates `<name>_<pid>` and applies every migration.
+    pub async fn migrated(name: &str) -> Self {
+        let db = Self::empty(name).await;
+        MigrationRunner::new(db.client.clone(), db.target.clone()).apply().await.unwrap();
+        db
+    }
+}
+
+impl Drop for TestDatabase {
+    fn drop(&mut self) {
+        // Drop cannot await, so the statement runs on a thread with its own runtime and client.
+        let url = self.url.clone();
+        let sql = format!("DROP DATABASE IF EXISTS {}", self.target.database());
+        let _ = std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+            runtime.block_on(clickhouse::Client::default().with_url(url).query(&sql).execute())
+        })
+        .join();
+    }
+}
@@ -0,0 +1,192 @@
+use chrono::{Datelike, NaiveDate, TimeZone, Utc};
+use project_models::data_models::calendar_data::{MarketSession, SessionKind, TradingCalendar};
+use project_models::data_models::futures_data::{Adjustment, ContinuousFutures, FuturesData, RollRule};
+use project_models::data_models::hist_data::{HistData, Interval};
+use project_models::data_models::instrument_data::BaseExchange;
+use std::collections::HashMap;
+
+mod common;
+use common::{date, future};
+
+/// Daily candles, one per `(day, close, volume)`, all in December 2024.
+fn candles(tradingsymbol: &str, days: &[(u32, f64, u64)]) -> Vec<HistData> {
+    days.iter()
+        .map(|&(day, close, volume)| HistData {
+            tradingsymbol: tradingsymbol.to_string(),
+            open: close - 1.0,
+            high: close + 1.0,
+            low: close - 2.0,
+            close,
+            volume,
+            datetime: Utc.with_ymd_and_hms(2024, 12, day, 3, 45, 0).unwrap(),
+            interval: Interval::Day,
+        })
+        .collect()
+}
+
+const FRONT: (i32, u32, u32) = (2024, 12, 12);
+const NEXT: (i32, u32, u32) = (2024, 12, 26);
+
+/// Weekly-style expiries on Thu 12th and Thu 26th. The front contract trades at 100, the next at 110.
+fn data() -> (Vec<FuturesData>, HashMap<NaiveDate, Vec<HistData>>) {
+    let front = date(FRONT.0, FRONT.1, FRONT.2);
+    let next = date(NEXT.0, NEXT.1, NEXT.2);
+    let futures = vec![
+        future("NIFTY", next, vec![]),
+        future("NIFTY", front, vec![]),
+        future("NIFTY", date(2025, 1, 30), vec![]),
+    ];
+
+    let mut by_expiry = HashMap::new();
+    by_expiry.insert(
+        front,
+        candles(
+            "NIFTY24DEC12FUT",
+            &[(5, 100.0, 900), (6, 100.0, 800), (9, 100.0, 700), (10, 100.0, 300), (11, 100.0, 100), (12, 100.0, 50)],
+        ),
+    );
+    by_expiry.insert(
+        next,
+        candles(
+            "NIFTY24DEC26FUT",
+            &[(5, 110.0, 100), (6, 110.0, 200), (9, 110.0, 500), (10, 110.0, 600), (11, 110.0, 700), (12, 110.0, 800), (13, 110.0, 900)],
+        ),
+    );
+    (futures, by_expiry)
+}
+
+#[test]
+fn test_days_before_expiry() {
+    let (futures, candles) = data();
+    let series = ContinuousFutures::new(RollRule::DaysBeforeExpiry(2)).build(BaseExchange::Nse, "NIFTY", &futures, &candles);
+
+    // Tue 10th is two trading days before Thu 12th.
+    assert_eq!(series.roll_dates(), vec![date(2024, 12, 10)]);
+    let roll = &series.rolls[0];
+    assert_eq!(roll.from_expiry, date(FRONT.0, FRONT.1, FRONT.2));
+    assert_eq!(roll.to_tradingsymbol, "NIFTY24DEC26FUT");
+    assert_eq!((roll.from_price, roll.to_price), (100.0, 110.0));
+
+    let symbols: Vec<&str> = series.candles.iter().map(|c| c.tradingsymbol.as_str()).collect();
+    assert_eq!(symbols[..3], ["NIFTY24DEC12FUT"; 3]);
+    assert_eq!(symbols[3..], ["NIFTY24DEC26FUT"; 4]);
+    assert_eq!(series.candles[0].close, 100.0);
+}
+
+#[test]
+fn test_calendar_moves_the_roll() {
+    let (futures, candles) = data();
+    let mut calendar = TradingCalendar::new(BaseExchange::Nse);
+    calendar.add_holiday(date(2024, 12, 11));
+    let build = |calendar: &TradingCalendar| {
+        ContinuousFutures::new(RollRule::DaysBeforeExpiry(2))
+            .with_calendar(calendar.clone())
+            .build(BaseExchange::Nse, "NIFTY", &futures, &candles)
+    };
+    assert_eq!(build(&calendar).roll_dates(), vec![date(2024, 12, 9)]);
+
+    // A special session on the holiday makes it count again.
+    let muhurat = MarketSession::parse(SessionKind::Muhurat, "18:00", "19:00").unwrap();
+    calendar.add_special_session(date(2024, 12, 11), muhurat);
+    assert_eq!(build(&calendar).roll_dates(), vec![date(2024, 12, 10)]);
+
+    // A calendar for another exchange is not used.
+    let series = ContinuousFutures::new(RollRule::DaysBeforeExpiry(2))
+        .with_calendar(TradingCalendar::new(BaseExchange::Bse))
+        .build(BaseExchange::Nse, "NIFTY", &futures, &candles);
+    assert_eq!(series.roll_dates(), vec![date(2024, 12, 10)]);
+}
+
+#[test]
+fn test_volume_crossover() {
+    let (futures, candles) = data();
+    let series = ContinuousFutures::new(RollRule::VolumeCrossover).build(BaseExchange::Nse, "NIFTY", &futures, &candles);
+    assert_eq!(series.roll_dates(), vec![date(2024, 12, 10)]);
+}
+
+#[test]
+fn test_rolls_after_expiry() {
+    let (futures, mut candles) = data();
+    // The next contract never out-trades the front one, so it takes over after expiry.
+    for candle in candles.get_mut(&date(NEXT.0, NEXT.1, NEXT.2)).unwrap() {
+        candle.volume = 1;
+    }
+    let series = ContinuousFutures::new(RollRule::VolumeCrossover).build(BaseExchange::Nse, "NIFTY", &futures, &candles);
+    assert_eq!(series.roll_dates(), vec![date(2024, 12, 13)]);
+    assert_eq!(series.candles.len(), 7);
+}
+
+#[test]
+fn test_adjustments() {
+    let (futures, candles) = data();
+    let build = |adjustment| {
+        ContinuousFutures::new(RollRule::DaysBeforeExpiry(2))
+            .with_adjustment(adjustment)
+            .build(BaseExchange::Nse, "NIFTY", &futures, &candles)
+    };
+
+    let raw = build(Adjustment::None);
+    let difference = build(Adjustment::Difference);
+    let ratio = build(Adjustment::Ratio);
+
+    assert_eq!(raw.candles[0].close, 100.0);
+    assert_eq!(difference.candles[0].close, 110.0);
+    assert_eq!(difference.candles[0].low, 108.0);
+    assert!((ratio.candles[0].close - 110.0).abs() < 1e-9);
+    assert!((ratio.candles[0].high - 111.1).abs() < 1e-9);
+
+    // The latest contract keeps its traded prices.
+    for series in [&raw, &difference, &ratio] {
+        assert_eq!(series.candles.last().unwrap().close, 110.0);
+        assert_eq!(series.rolls, raw.rolls);
+    }
+}
+
+#[test]
+fn test_other_underlyings_and_missing_candles() {
+    let (futures, candles) = data();
+    let series = ContinuousFutures::new(RollRule::DaysBeforeExpiry(2)).build(BaseExchange::Nse, "BANKNIFTY", &futures, &candles);
+    assert!(series.candles.is_empty() && series.rolls.is_empty());
+
+    // The January expiry has no candles and is skipped.
+    let series = ContinuousFutures::new(RollRule::DaysBeforeExpiry(0)).build(BaseExchange::Nse, "NIFTY", &futures, &candles);
+    assert_eq!(series.rolls.len(), 1);
+    assert_eq!(series.roll_dates(), vec![date(2024, 12, 12)]);
+}
+
+#[test]
+fn test_contracts_without_a_roll_candle_are_skipped() {
+    let (futures, mut by_expiry) = data();
+    let next = date(NEXT.0, NEXT.1, NEXT.2);
+    let jan = date(2025, 1, 30);
+    // The December 26th data stops before any roll day; the series rolls into January instead.
+    by_expiry.get_mut(&next).unwrap().retain(|c| c.datetime.day() <= 6);
+    by_expiry.insert(jan, candles("NIFTY25JANFUT", &[(9, 120.0, 10), (10, 120.0, 10), (11, 120.0, 10)]));
+
+    let series = ContinuousFutures::new(RollRule::DaysBeforeExpiry(2)).build(BaseExchange::Nse, "NIFTY", &futures, &by_expiry);
+    assert_eq!(series.rolls.len(), 1);
+    assert_eq!((series.rolls[0].to_expiry, series.roll_dates()[0]), (jan, date(2024, 12, 10)));
+    let symbols: Vec<&str> = series.candles.iter().map(|c| c.tradingsymbol.as_str()).collect();
+    assert_eq!(symbols, ["NIFTY24DEC12FUT", "NIFTY24DEC12FUT", "NIFTY24DEC12FUT", "NIFTY25JANFUT", "NIFTY25JANFUT"]);
+}
+
+#[test]
+fn test_other_exchanges_are_not_merged() {
+    let (mut futures, mut by_expiry) = data();
+    // A BSE-listed NIFTY contract expiring between the two NSE ones.
+    let bse = date(2024, 12, 19);
+    futures.push(FuturesData {
+        base_exchange: BaseExchange::Bse,
+        ..future("NIFTY", bse, vec![])
+    });
+    by_expiry.insert(bse, candles("NIFTY24DEC19FUT", &[(9, 120.0, 5000), (10, 120.0, 5000)]));
+
+    let series = ContinuousFutures::new(RollRule::VolumeCrossover).build(BaseExchange::Nse, "NIFTY", &futures, &by_expiry);
+    assert_eq!(series.base_exchange, BaseExchange::Nse);
+    assert!(series.rolls.iter().all(|r| r.to_expiry != bse));
+    assert_eq!(series.roll_dates(), vec![date(2024, 12, 10)]);
+
+    let series = ContinuousFutures::new(RollRule::VolumeCrossover).build(BaseExchange::Bse, "NIFTY", &futures, &by_expiry);
+    assert!(series.rolls.is_empty());
+    assert!(series.candles.iter().all(|c| c.tradingsymbol == "NIFTY24DEC19FUT"));
+}
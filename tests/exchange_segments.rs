@@ -0,0 +1,84 @@
+use project_models::data_models::instrument_data::{BaseExchange, Exchange, Instrument, InstrumentError, Segment};
+use serde_json::json;
+
+#[test]
+fn test_segments_belong_to_their_exchange() {
+    for i in 0..10 {
+        let exchange = Exchange::try_from(i).unwrap();
+        for segment in exchange.segments() {
+            assert!(exchange.has_segment(*segment));
+            if let Some(owner) = segment.exchange() {
+                assert_eq!(owner, exchange);
+            }
+        }
+    }
+
+    for i in 0..16 {
+        let segment = Segment::try_from(i).unwrap();
+        if let Some(exchange) = segment.exchange() {
+            assert!(exchange.segments().contains(&segment), "{} not listed under {}", segment, exchange);
+        }
+    }
+}
+
+#[test]
+fn test_base_exchange() {
+    assert_eq!(Exchange::Nfo.base_exchange(), BaseExchange::Nse);
+    assert_eq!(Exchange::Cds.base_exchange(), BaseExchange::Nse);
+    assert_eq!(Exchange::Bfo.base_exchange(), BaseExchange::Bse);
+    assert_eq!(Exchange::Mcx.base_exchange(), BaseExchange::Mcx);
+    assert_eq!(Exchange::Global.base_exchange(), BaseExchange::Global);
+}
+
+#[test]
+fn test_segment_kinds() {
+    assert!(Segment::NfoOpt.is_derivative());
+    assert!(Segment::NfoOpt.is_option());
+    assert!(Segment::McxFut.is_future());
+    assert!(!Segment::Nse.is_derivative());
+    assert!(!Segment::Indices.is_derivative());
+    assert_eq!(Segment::Indices.exchange(), None);
+    assert_eq!(Segment::BcdOpt.exchange(), Some(Exchange::Bcd));
+}
+
+fn nifty_future(exchange: &str, segment: &str) -> serde_json::Value {
+    json!({
+        "instrument_token": "13368066",
+        "exchange_token": "52219",
+        "tradingsymbol": "NIFTY24DECFUT",
+        "name": "NIFTY",
+        "expiry": "2024-12-26",
+        "lot_size": 25,
+        "instrument_type": "FUT",
+        "segment": segment,
+        "exchange": exchange
+    })
+}
+
+#[test]
+fn test_mismatched_segment_is_rejected() {
+    let instrument = Instrument::from_source(&nifty_future("NFO", "NFO-FUT")).unwrap();
+    assert_eq!(instrument.base_exchange, BaseExchange::Nse);
+
+    let err = Instrument::from_source(&nifty_future("NSE", "NFO-FUT")).unwrap_err();
+    assert_eq!(
+        err,
+        InstrumentError::SegmentMismatch {
+            exchange: Exchange::Nse,
+            segment: Segment::NfoFut
+        }
+    );
+    assert_eq!(err.to_string(), "Segment NFO-FUT does not belong to exchange NSE");
+}
+
+#[test]
+fn test_validate_checks_base_exchange() {
+    let mut instrument = Instrument::from_source(&nifty_future("NFO", "NFO-FUT")).unwrap();
+    assert!(instrument.validate().is_ok());
+
+    instrument.base_exchange = BaseExchange::Bse;
+    assert!(matches!(
+        instrument.validate(),
+        Err(InstrumentError::BaseExchangeMismatch { exchange: Exchange::Nfo, base_exchange: BaseExchange::Bse })
+    ));
+}
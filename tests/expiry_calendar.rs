@@ -0,0 +1,100 @@
+use project_models::data_models::calendar_data::{MarketSession, SessionKind, TradingCalendar};
+use project_models::data_models::futures_data::{ExpiryCalendar, ExpiryKind};
+use project_models::data_models::instrument_data::{BaseExchange, InstrumentCsvReader};
+
+mod common;
+use common::{date, future};
+
+fn nifty() -> ExpiryCalendar {
+    ExpiryCalendar::new(
+        "NIFTY",
+        vec![
+            date(2024, 12, 5),
+            date(2024, 12, 12),
+            date(2024, 12, 19),
+            date(2024, 12, 26),
+            date(2025, 1, 2),
+            date(2025, 1, 30),
+            date(2025, 2, 27),
+            date(2025, 3, 27),
+            date(2025, 6, 26),
+            date(2025, 9, 25),
+            date(2025, 12, 24),
+            // Duplicates from several instruments collapse.
+            date(2024, 12, 26),
+        ],
+    )
+}
+
+#[test]
+fn test_classifies_expiries() {
+    let calendar = nifty();
+    assert_eq!(calendar.expiries().len(), 11);
+
+    assert_eq!(calendar.kind(date(2024, 12, 19)), Some(ExpiryKind::Weekly));
+    assert_eq!(calendar.kind(date(2024, 12, 26)), Some(ExpiryKind::Monthly));
+    assert_eq!(calendar.kind(date(2025, 1, 2)), Some(ExpiryKind::Weekly));
+    assert_eq!(calendar.kind(date(2025, 3, 27)), Some(ExpiryKind::Monthly));
+    assert_eq!(calendar.kind(date(2025, 6, 26)), Some(ExpiryKind::Quarterly));
+    assert_eq!(calendar.kind(date(2025, 12, 24)), Some(ExpiryKind::Quarterly));
+    assert_eq!(calendar.kind(date(2025, 4, 24)), None);
+}
+
+#[test]
+fn test_current_next_far() {
+    let calendar = nifty();
+    let today = date(2024, 12, 20);
+
+    assert_eq!(calendar.nearest(today), Some(date(2024, 12, 26)));
+    assert_eq!(calendar.nearest(date(2024, 12, 27)), Some(date(2025, 1, 2)));
+    assert_eq!(calendar.current(today), Some(date(2024, 12, 26)));
+    assert_eq!(calendar.next(today), Some(date(2025, 1, 30)));
+    assert_eq!(calendar.far(today), Some(date(2025, 2, 27)));
+    assert_eq!(calendar.upcoming(date(2025, 7, 1)).count(), 2);
+    assert_eq!(calendar.current(date(2026, 1, 1)), None);
+}
+
+#[test]
+fn test_from_instruments_uses_derivatives_only() {
+    let dump = "\
+instrument_token,exchange_token,tradingsymbol,name,last_price,expiry,strike,tick_size,lot_size,instrument_type,segment,exchange
+256265,1001,NIFTY 50,NIFTY 50,0,,0,0,0,EQ,INDICES,NSE
+13368066,52219,NIFTY24DECFUT,NIFTY,0,2024-12-26,0,0.05,25,FUT,NFO-FUT,NFO
+10923010,42668,NIFTY24D1924000CE,NIFTY,0,2024-12-19,24000,0.05,25,CE,NFO-OPT,NFO
+1,1,BANKNIFTY24DEC52000CE,BANKNIFTY,0,2024-12-24,52000,0.05,15,CE,NFO-OPT,NFO
+";
+    let instruments: Vec<_> = InstrumentCsvReader::new(dump.as_bytes())
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    let calendar = ExpiryCalendar::from_instruments("NIFTY", &instruments);
+
+    assert_eq!(calendar.name(), "NIFTY");
+    assert_eq!(
+        calendar.expiries(),
+        &[(date(2024, 12, 19), ExpiryKind::Weekly), (date(2024, 12, 26), ExpiryKind::Monthly)]
+    );
+}
+
+#[test]
+fn test_trading_dte_skips_weekends_and_holidays() {
+    let mut calendar = TradingCalendar::new(BaseExchange::Nse);
+    calendar.add_holiday(date(2024, 12, 25));
+    assert!(!calendar.is_trading_day(date(2024, 12, 21)));
+    assert!(!calendar.is_trading_day(date(2024, 12, 25)));
+    assert!(calendar.is_trading_day(date(2024, 12, 26)));
+
+    // Fri 20th → Thu 26th: Mon, Tue, (Christmas), Thu.
+    assert_eq!(ExpiryCalendar::trading_dte(date(2024, 12, 20), date(2024, 12, 26), &calendar), 3);
+    assert_eq!(ExpiryCalendar::trading_dte(date(2024, 12, 26), date(2024, 12, 26), &calendar), 0);
+    assert_eq!(ExpiryCalendar::trading_dte(date(2024, 12, 26), date(2024, 12, 19), &calendar), 0);
+
+    let mut future = future("NIFTY", date(2024, 12, 26), vec![]);
+    future.set_trading_dte(date(2024, 12, 20), &calendar);
+    assert_eq!(future.dte, 3);
+
+    // A Saturday special session counts as a trading day.
+    let session = MarketSession::parse(SessionKind::Special, "10:00", "11:00").unwrap();
+    calendar.add_special_session(date(2024, 12, 21), session);
+    assert_eq!(ExpiryCalendar::trading_dte(date(2024, 12, 20), date(2024, 12, 26), &calendar), 4);
+}
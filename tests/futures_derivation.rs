@@ -0,0 +1,127 @@
+use chrono::{Days, NaiveDate};
+use project_models::data_models::futures_data::{FutureType, FuturesData, ADD_TO_BASE_MIN_DTE};
+use project_models::data_models::instrument_data::{BaseExchange, Instrument, InstrumentCsvReader};
+use project_models::data_models::repository::{FuturesRepo, InstrumentRepo};
+
+mod common;
+use common::{date, TestDatabase};
+
+const DUMP: &str = "\
+instrument_token,exchange_token,tradingsymbol,name,last_price,expiry,strike,tick_size,lot_size,instrument_type,segment,exchange
+2885633,11272,RELIANCE,RELIANCE,0,,0,0.05,1,EQ,NSE,NSE
+13238786,51714,RELIANCE24DECFUT,RELIANCE,0,2024-12-26,0,0.05,500,FUT,NFO-FUT,NFO
+10923010,42668,RELIANCE24DEC1300CE,RELIANCE,0,2024-12-26,1300,0.05,500,CE,NFO-OPT,NFO
+10923266,42669,RELIANCE24DEC1300PE,RELIANCE,0,2024-12-26,1300,0.05,500,PE,NFO-OPT,NFO
+10923522,42670,RELIANCE24DEC1250CE,RELIANCE,0,2024-12-26,1250,0.05,500,CE,NFO-OPT,NFO
+11923010,52668,RELIANCE25JAN1300CE,RELIANCE,0,2025-01-30,1300,0.05,500,CE,NFO-OPT,NFO
+12923010,62668,RELIANCE25MAR1400CE,RELIANCE,0,2025-03-27,1400,0.05,500,CE,NFO-OPT,NFO
+14000001,70001,NIFTY24D1924000CE,NIFTY,0,2024-12-19,24000,0.05,25,CE,NFO-OPT,NFO
+";
+
+fn instruments() -> Vec<Instrument> {
+    InstrumentCsvReader::new(DUMP.as_bytes()).unwrap().map(Result::unwrap).collect()
+}
+
+#[test]
+fn test_classify() {
+    assert_eq!(FutureType::classify(true, 90), FutureType::Liquid);
+    assert_eq!(FutureType::classify(false, ADD_TO_BASE_MIN_DTE), FutureType::Atm);
+    assert_eq!(FutureType::classify(false, ADD_TO_BASE_MIN_DTE + 1), FutureType::AddToBase);
+}
+
+#[test]
+fn test_from_instruments() {
+    let today = date(2024, 12, 16);
+    let futures = FuturesData::from_instruments(&instruments(), &[], today);
+
+    let keys: Vec<(&str, NaiveDate)> = futures.iter().map(|f| (f.name.as_str(), f.expiry)).collect();
+    assert_eq!(
+        keys,
+        vec![
+            ("NIFTY", date(2024, 12, 19)),
+            ("RELIANCE", date(2024, 12, 26)),
+            ("RELIANCE", date(2025, 1, 30)),
+            ("RELIANCE", date(2025, 3, 27)),
+        ]
+    );
+
+    let dec = &futures[1];
+    assert_eq!(dec.base_exchange, BaseExchange::Nse);
+    assert_eq!(dec.dte, 10);
+    assert_eq!(dec.future_type, FutureType::Liquid);
+    assert_eq!(dec.underlying, 2885633);
+    assert_eq!(dec.base_expiry, None);
+    assert_eq!(dec.strike, vec![1250.0, 1300.0]);
+    assert_eq!(dec.add_to_base, 0.0);
+
+    let jan = &futures[2];
+    assert_eq!(jan.dte, 45);
+    assert_eq!(jan.future_type, FutureType::AddToBase);
+    assert_eq!(jan.base_expiry, Some(date(2024, 12, 26)));
+    assert_eq!(futures[3].base_expiry, Some(date(2025, 1, 30)));
+
+    // NIFTY has no EQ row here, so no underlying, and nothing precedes its only expiry.
+    assert_eq!(futures[0].underlying, 0);
+    assert_eq!(futures[0].future_type, FutureType::Atm);
+    assert_eq!(futures[0].base_expiry, None);
+}
+
+#[test]
+fn test_add_to_base_is_kept() {
+    let today = date(2024, 12, 16);
+    let mut existing = FuturesData::from_instruments(&instruments(), &[], today);
+    existing[2].add_to_base = 12.5;
+    // A stale row whose base expiry no longer matches is not carried over.
+    existing[3].add_to_base = 7.0;
+    existing[3].base_expiry = Some(date(2024, 12, 26));
+
+    let futures = FuturesData::from_instruments(&instruments(), &existing, today);
+    assert_eq!(futures[2].add_to_base, 12.5);
+    assert_eq!(futures[3].add_to_base, 0.0);
+}
+
+#[test]
+fn test_instruments_without_a_name_are_skipped() {
+    let mut nameless = instruments();
+    for instrument in nameless.iter_mut().filter(|i| i.name.as_deref() == Some("NIFTY")) {
+        instrument.name = None;
+    }
+    let futures = FuturesData::from_instruments(&nameless, &[], date(2024, 12, 16));
+    assert!(futures.iter().all(|f| f.name == "RELIANCE"));
+    assert_eq!(futures.len(), 3);
+}
+
+#[test]
+fn test_dte_is_absolute() {
+    let futures = FuturesData::from_instruments(&instruments(), &[], date(2024, 12, 29));
+    assert_eq!(futures[1].dte, 3);
+}
+
+/// Needs a local server: `CLICKHOUSE_URL=http://localhost:8123 cargo test --test futures_derivation -- --ignored`
+#[tokio::test]
+#[ignore]
+async fn test_matches_populate_future_staging() {
+    let db = TestDatabase::migrated("futures_derivation").await;
+    let (client, target) = (&db.client, &db.target);
+
+    InstrumentRepo::new(client.clone(), target.clone())
+        .replace_all(&instruments())
+        .await
+        .unwrap();
+    let repo = FuturesRepo::new(client.clone(), target.clone());
+    repo.rebuild_staging().await.unwrap();
+    let mut from_sql = repo.staging().fetch_all().await.unwrap();
+    // The SQL groups NULL names into rows of their own; `from_instruments` skips them.
+    from_sql.retain(|f| !f.name.is_empty());
+    from_sql.sort_by(|a, b| (a.name.as_str(), a.expiry).cmp(&(b.name.as_str(), b.expiry)));
+
+    // The SQL measures DTE from the server's date.
+    let day_num = client
+        .query("SELECT toUInt64(toRelativeDayNum(now()))")
+        .fetch_one::<u64>()
+        .await
+        .unwrap();
+    let today = NaiveDate::default() + Days::new(day_num);
+
+    assert_eq!(FuturesData::from_instruments(&instruments(), &[], today), from_sql);
+}
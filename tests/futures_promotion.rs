@@ -0,0 +1,105 @@
+use project_models::data_models::futures_data::{
+    FutureType, FuturesData, FuturesDiff, FuturesField, PromotionRules, StagingIssue,
+};
+use project_models::data_models::instrument_data::BaseExchange;
+use project_models::data_models::repository::{FuturesRepo, RepoError, SwapMethod};
+
+mod common;
+use common::{date, future, TestDatabase};
+
+#[test]
+fn test_diff() {
+    let dec = future("RELIANCE", date(2024, 12, 26), vec![1300.0]);
+    let jan = future("RELIANCE", date(2025, 1, 30), vec![1300.0]);
+    let nov = future("RELIANCE", date(2024, 11, 28), vec![1250.0]);
+
+    let mut new_dec = dec.clone();
+    new_dec.dte = 9;
+    new_dec.strike.push(1350.0);
+    new_dec.add_to_base = 4.0;
+    let mut new_jan = jan.clone();
+    new_jan.dte = 44;
+
+    let diff = FuturesDiff::between(&[nov.clone(), dec.clone(), jan], &[new_dec.clone(), new_jan]);
+    assert_eq!(diff.removed, vec![nov]);
+    assert!(diff.added.is_empty());
+    // A DTE change alone is not a modification.
+    assert_eq!(diff.modified.len(), 1);
+    assert_eq!(diff.modified[0].new, new_dec);
+    assert_eq!(diff.modified[0].changed, vec![FuturesField::AddToBase, FuturesField::Strike]);
+    assert_eq!(FuturesField::AddToBase.to_string(), "add_to_base");
+
+    let unchanged = [dec];
+    assert!(FuturesDiff::between(&unchanged, &unchanged).is_empty());
+}
+
+#[test]
+fn test_validation() {
+    let rules = PromotionRules::default();
+    let live: Vec<FuturesData> = (1..=4).map(|d| future("NIFTY", date(2025, 1, d), vec![24000.0])).collect();
+
+    assert!(rules.validate(&live, &live).is_empty());
+    assert_eq!(
+        rules.validate(&[], &live),
+        vec![
+            StagingIssue::TooFewRows { staging: 0, min_rows: 1 },
+            StagingIssue::ShrankTooMuch { staging: 0, live: 4 },
+        ]
+    );
+    assert!(rules.validate(&live[..2], &live).is_empty());
+    assert_eq!(rules.validate(&live[..1], &live), vec![StagingIssue::ShrankTooMuch { staging: 1, live: 4 }]);
+
+    // A liquid future without options is fine; an ATM one is not.
+    let mut staging = vec![future("NIFTY", date(2025, 1, 30), vec![]), future("BANKNIFTY", date(2025, 1, 30), vec![])];
+    staging[0].future_type = FutureType::Atm;
+    let issues = rules.validate(&staging, &[]);
+    assert_eq!(
+        issues,
+        vec![StagingIssue::MissingStrikes {
+            base_exchange: BaseExchange::Nse,
+            name: "NIFTY".to_string(),
+            expiry: date(2025, 1, 30),
+        }]
+    );
+    assert_eq!(
+        RepoError::StagingRejected(issues).to_string(),
+        "Staging rejected: NSE NIFTY 2025-01-30 has no strikes"
+    );
+
+    let lenient = PromotionRules {
+        require_strikes: false,
+        ..PromotionRules::default()
+    };
+    assert!(lenient.validate(&staging, &[]).is_empty());
+}
+
+/// Needs a local server: `CLICKHOUSE_URL=http://localhost:8123 cargo test --test futures_promotion -- --ignored`
+#[tokio::test]
+#[ignore]
+async fn test_live_promotion() {
+    let db = TestDatabase::migrated("futures_promotion").await;
+    let (client, target) = (&db.client, &db.target);
+
+    let old = vec![future("NIFTY", date(2024, 12, 26), vec![24000.0])];
+    let new = vec![
+        future("NIFTY", date(2024, 12, 26), vec![24000.0, 24100.0]),
+        future("NIFTY", date(2025, 1, 30), vec![24000.0]),
+    ];
+
+    for swap in [SwapMethod::Exchange, SwapMethod::Rename] {
+        let repo = FuturesRepo::new(client.clone(), target.clone()).with_swap(swap);
+        repo.replace_all(&old).await.unwrap();
+        repo.staging().replace(&new).await.unwrap();
+
+        let promotion = repo.promote_staging().await.unwrap();
+        assert_eq!((promotion.previous_rows, promotion.promoted_rows), (1, 2));
+        assert_eq!(promotion.diff.added.len(), 1);
+        assert_eq!(promotion.diff.modified[0].changed, vec![FuturesField::Strike]);
+        // The previous contents are left in staging.
+        assert_eq!(repo.staging().count().await.unwrap(), 1);
+
+        repo.staging().truncate().await.unwrap();
+        assert!(matches!(repo.promote_staging().await, Err(RepoError::StagingRejected(_))));
+        assert_eq!(repo.futures().count().await.unwrap(), 2);
+    }
+}
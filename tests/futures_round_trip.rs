@@ -0,0 +1,64 @@
+use project_models::data_models::futures_data::{FutureType, FuturesData};
+use project_models::data_models::instrument_data::BaseExchange;
+use project_models::data_models::migrations::{check_schema_drift, MIGRATIONS};
+use project_models::data_models::repository::FuturesRepo;
+use project_models::data_models::{ClickHouseTable, SchemaTarget};
+
+mod common;
+use common::{date, future, TestDatabase};
+
+fn futures() -> Vec<FuturesData> {
+    let nearest = future("RELIANCE", date(2024, 12, 26), vec![1250.0, 1300.0]);
+    let far = FuturesData {
+        expiry: date(2025, 3, 27),
+        dte: 101,
+        future_type: FutureType::AddToBase,
+        base_expiry: Some(date(2024, 12, 26)),
+        add_to_base: 12.75,
+        strike: vec![1400.0],
+        ..nearest.clone()
+    };
+    vec![nearest, far]
+}
+
+#[test]
+fn test_migration_matches_model() {
+    let target = SchemaTarget::new("data");
+    let base_expiry = FuturesData::columns()
+        .into_iter()
+        .find(|(name, _)| *name == "base_expiry")
+        .map(|(_, ty)| ty)
+        .unwrap();
+
+    let altered: Vec<String> = MIGRATIONS
+        .iter()
+        .flat_map(|m| m.statements(&target))
+        .filter(|s| s.contains("MODIFY COLUMN base_expiry"))
+        .collect();
+    assert_eq!(altered.len(), 2);
+    for statement in altered {
+        assert!(statement.ends_with(&format!("base_expiry {}", base_expiry)), "{}", statement);
+    }
+}
+
+/// Needs a local server: `CLICKHOUSE_URL=http://localhost:8123 cargo test --test futures_round_trip -- --ignored`
+#[tokio::test]
+#[ignore]
+async fn test_insert_and_select() {
+    let db = TestDatabase::migrated("futures_round_trip").await;
+    let (client, target) = (&db.client, &db.target);
+    let drift = check_schema_drift(client, target).await.unwrap();
+
+    let repo = FuturesRepo::new(client.clone(), target.clone());
+    repo.insert(&futures()).await.unwrap();
+    let mut stored = repo.fetch_all().await.unwrap();
+    let far = repo
+        .get(BaseExchange::Nse, "RELIANCE", date(2025, 3, 27))
+        .await
+        .unwrap();
+
+    assert!(drift.is_clean(), "{}", drift);
+    stored.sort_by_key(|f| f.expiry);
+    assert_eq!(stored, futures());
+    assert_eq!(far.unwrap().base_expiry, Some(date(2024, 12, 26)));
+}
@@ -0,0 +1,97 @@
+use project_models::config_models::{Config, IndexAliasConfig};
+use project_models::data_models::instrument_data::{IndexAliases, Instrument, InstrumentCsvReader};
+use serde_json::json;
+use tempfile::TempDir;
+
+#[test]
+fn test_builtin_aliases_both_directions() {
+    let aliases = IndexAliases::default();
+    assert_eq!(aliases.symbol("NIFTY BANK"), Some("BANKNIFTY"));
+    assert_eq!(aliases.display_name("BANKNIFTY"), Some("NIFTY BANK"));
+    assert_eq!(aliases.canonical("NIFTY 50"), "NIFTY");
+    assert_eq!(aliases.canonical("RELIANCE"), "RELIANCE");
+    assert_eq!(aliases.display_name("RELIANCE"), None);
+}
+
+#[test]
+fn test_insert_replaces_reverse_mapping() {
+    let mut aliases = IndexAliases::empty();
+    aliases.insert("NIFTY IT", "NIFTYIT");
+    aliases.insert("NIFTY IT", "CNXIT");
+
+    assert_eq!(aliases.symbol("NIFTY IT"), Some("CNXIT"));
+    assert_eq!(aliases.display_name("CNXIT"), Some("NIFTY IT"));
+    assert_eq!(aliases.display_name("NIFTYIT"), None);
+    assert_eq!(aliases.len(), 1);
+}
+
+#[test]
+fn test_from_config_layers_file_and_inline_aliases() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("index_aliases.toml");
+    std::fs::write(&file, "\"NIFTY IT\" = \"NIFTYIT\"\n\"S&P BSE SENSEX\" = \"SENSEX\"\n").unwrap();
+
+    let config: Config = toml::from_str(&format!(
+        r#"
+        [index_aliases]
+        file = "{}"
+
+        [index_aliases.aliases]
+        "S&P BSE SENSEX" = "BSESENSEX"
+        "#,
+        file.display()
+    ))
+    .unwrap();
+
+    let aliases = IndexAliases::from_config(&config).unwrap();
+    assert_eq!(aliases.symbol("NIFTY IT"), Some("NIFTYIT"));
+    assert_eq!(aliases.symbol("S&P BSE SENSEX"), Some("BSESENSEX"));
+    assert_eq!(aliases.symbol("NIFTY BANK"), Some("BANKNIFTY"));
+    assert_eq!(aliases, IndexAliases::from_section(config.index_aliases.as_ref().unwrap()).unwrap());
+
+    let without_section: Config = toml::from_str("").unwrap();
+    assert_eq!(IndexAliases::from_config(&without_section).unwrap(), IndexAliases::default());
+}
+
+#[test]
+fn test_missing_alias_file_is_an_error() {
+    let config = IndexAliasConfig {
+        file: Some("/nonexistent/index_aliases.toml".to_string()),
+        ..Default::default()
+    };
+    assert!(IndexAliases::from_section(&config).is_err());
+}
+
+#[test]
+fn test_aliases_applied_during_parsing() {
+    let item = json!({
+        "instrument_token": "259849",
+        "exchange_token": "1015",
+        "tradingsymbol": "NIFTY IT",
+        "name": "NIFTY IT",
+        "lot_size": 0,
+        "instrument_type": "EQ",
+        "segment": "INDICES",
+        "exchange": "NSE"
+    });
+
+    assert_eq!(Instrument::from_json(&item).unwrap().name.as_deref(), Some("NIFTY IT"));
+
+    let mut aliases = IndexAliases::default();
+    aliases.insert("NIFTY IT", "NIFTYIT");
+    let instrument = Instrument::from_source_with(&item, &aliases).unwrap();
+    assert_eq!(instrument.name.as_deref(), Some("NIFTYIT"));
+    assert_eq!(Instrument::from_json_with(&item, &aliases).unwrap(), instrument);
+
+    let dump = "\
+instrument_token,exchange_token,tradingsymbol,name,lot_size,instrument_type,segment,exchange
+259849,1015,NIFTY IT,NIFTY IT,0,EQ,INDICES,NSE
+";
+    let instrument = InstrumentCsvReader::new(dump.as_bytes())
+        .unwrap()
+        .with_aliases(aliases)
+        .next()
+        .unwrap()
+        .unwrap();
+    assert_eq!(instrument.name.as_deref(), Some("NIFTYIT"));
+}
@@ -0,0 +1,123 @@
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use project_models::data_models::instrument_data::{
+    Exchange, Instrument, InstrumentCsvError, InstrumentCsvReader, InstrumentError, InstrumentType, Segment,
+};
+use serde_json::json;
+use std::io::{Cursor, Write};
+use tempfile::TempDir;
+
+const DUMP: &str = "\
+instrument_token,exchange_token,tradingsymbol,name,last_price,expiry,strike,tick_size,lot_size,instrument_type,segment,exchange
+256265,1001,NIFTY 50,NIFTY 50,0,,0,0,0,EQ,INDICES,NSE
+13368066,52219,NIFTY24DECFUT,NIFTY,0,2024-12-26,0,0.05,25,FUT,NFO-FUT,NFO
+10923010,42668,NIFTY24D1924000CE,NIFTY,0,2024-12-19,24000,0.05,25,CE,NFO-OPT,NFO
+";
+
+#[test]
+fn test_reads_plain_dump() {
+    let reader = InstrumentCsvReader::new(DUMP.as_bytes()).unwrap();
+    let (instruments, errors) = reader.read_all();
+
+    assert!(errors.is_empty(), "{:?}", errors);
+    assert_eq!(instruments.len(), 3);
+
+    let index = &instruments[0];
+    assert_eq!(index.name.as_deref(), Some("NIFTY"));
+    assert_eq!(index.expiry, None);
+    assert_eq!(index.segment, Segment::Indices);
+
+    let call = &instruments[2];
+    assert_eq!(call.instrument_token, "10923010");
+    assert_eq!(call.exchange, Exchange::Nfo);
+    assert_eq!(call.instrument_type, InstrumentType::Ce);
+    assert_eq!(call.strike, 24000.0);
+    assert_eq!(call.lot_size, 25);
+    assert_eq!(call.expiry, chrono::NaiveDate::from_ymd_opt(2024, 12, 19));
+}
+
+#[test]
+fn test_reads_gzip_dump_from_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("instruments.csv.gz");
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(DUMP.as_bytes()).unwrap();
+    std::fs::write(&path, encoder.finish().unwrap()).unwrap();
+
+    let (instruments, errors) = InstrumentCsvReader::open(&path).unwrap().read_all();
+    assert!(errors.is_empty(), "{:?}", errors);
+    assert_eq!(instruments.len(), 3);
+}
+
+#[test]
+fn test_detects_plain_stream() {
+    let reader = InstrumentCsvReader::detect(Cursor::new(DUMP.as_bytes().to_vec())).unwrap();
+    assert_eq!(reader.count(), 3);
+}
+
+#[test]
+fn test_bad_rows_are_reported_with_line_numbers() {
+    let dump = "\
+instrument_token,exchange_token,tradingsymbol,name,last_price,expiry,strike,tick_size,lot_size,instrument_type,segment,exchange
+13368066,52219,NIFTY24DECFUT,NIFTY,0,2024-12-26,0,0.05,25,FUT,NFO-FUT,NFO
+1,2,BROKEN,NIFTY,0,2024-12-26,0,0.05,25,FUT,XYZ-FUT,NFO
+3,4,SHORT,NIFTY
+5,6,BADLOT,NIFTY,0,2024-12-26,0,0.05,lots,FUT,NFO-FUT,NFO
+10923010,42668,NIFTY24D1924000CE,NIFTY,0,2024-12-19,24000,0.05,25,CE,NFO-OPT,NFO
+";
+    let (instruments, errors) = InstrumentCsvReader::new(dump.as_bytes()).unwrap().read_all();
+
+    assert_eq!(instruments.len(), 2);
+    assert_eq!(errors.len(), 3);
+    assert_eq!(errors.iter().map(|e| e.line()).collect::<Vec<_>>(), vec![Some(3), Some(4), Some(5)]);
+
+    assert!(matches!(
+        &errors[0],
+        InstrumentCsvError::Row { error: InstrumentError::Invalid { field: "segment", .. }, .. }
+    ));
+    assert!(matches!(&errors[1], InstrumentCsvError::Csv { .. }));
+    assert!(matches!(
+        &errors[2],
+        InstrumentCsvError::Row { error: InstrumentError::Invalid { field: "lot_size", .. }, .. }
+    ));
+}
+
+#[test]
+fn test_csv_and_json_share_mapping() {
+    let from_csv = InstrumentCsvReader::new(DUMP.as_bytes())
+        .unwrap()
+        .nth(1)
+        .unwrap()
+        .unwrap();
+
+    let from_json = Instrument::from_json(&json!({
+        "instrument_token": "13368066",
+        "exchange_token": "52219",
+        "tradingsymbol": "NIFTY24DECFUT",
+        "name": "NIFTY",
+        "last_price": 0.0,
+        "expiry": "2024-12-26",
+        "strike": 0.0,
+        "tick_size": 0.05,
+        "lot_size": 25,
+        "instrument_type": "FUT",
+        "segment": "NFO-FUT",
+        "exchange": "NFO"
+    }))
+    .unwrap();
+
+    assert_eq!(from_csv, from_json);
+}
+
+#[test]
+fn test_missing_required_column() {
+    let dump = "\
+exchange_token,tradingsymbol,lot_size,instrument_type,segment,exchange
+52219,NIFTY24DECFUT,25,FUT,NFO-FUT,NFO
+";
+    let (_, errors) = InstrumentCsvReader::new(dump.as_bytes()).unwrap().read_all();
+    assert!(matches!(
+        &errors[0],
+        InstrumentCsvError::Row { line: 2, error: InstrumentError::Missing("instrument_token") }
+    ));
+}
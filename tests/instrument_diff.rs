@@ -0,0 +1,61 @@
+use project_models::data_models::instrument_data::{
+    InstrumentCsvReader, InstrumentDiff, InstrumentField, InstrumentMaster,
+};
+
+const HEADER: &str = "instrument_token,exchange_token,tradingsymbol,name,last_price,expiry,strike,tick_size,lot_size,instrument_type,segment,exchange\n";
+
+fn master(rows: &str) -> InstrumentMaster {
+    InstrumentCsvReader::new(format!("{}{}", HEADER, rows).as_bytes())
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+}
+
+#[test]
+fn test_added_removed_and_modified() {
+    let yesterday = master(
+        "\
+10923010,42668,NIFTY24D1924000CE,NIFTY,0,2024-12-19,24000,0.05,25,CE,NFO-OPT,NFO
+13368066,52219,NIFTY24DECFUT,NIFTY,0,2024-12-26,0,0.05,25,FUT,NFO-FUT,NFO
+738561,2885,RELIANCE,RELIANCE INDUSTRIES,1250.5,,0,0.05,1,EQ,NSE,NSE
+",
+    );
+    let today = master(
+        "\
+13368066,52219,NIFTY24DECFUT,NIFTY,0,2024-12-26,0,0.1,75,FUT,NFO-FUT,NFO
+738561,2885,RELIANCE,RELIANCE INDUSTRIES,1262.0,,0,0.05,1,EQ,NSE,NSE
+10923522,42670,NIFTY24D2624000CE,NIFTY,0,2024-12-26,24000,0.05,75,CE,NFO-OPT,NFO
+",
+    );
+
+    let diff = InstrumentDiff::between(&yesterday, &today);
+
+    assert_eq!(diff.added.len(), 1);
+    assert_eq!(diff.added[0].tradingsymbol, "NIFTY24D2624000CE");
+    assert_eq!(diff.removed.len(), 1);
+    assert_eq!(diff.removed[0].tradingsymbol, "NIFTY24D1924000CE");
+
+    // The RELIANCE price move alone is not a modification.
+    assert_eq!(diff.modified.len(), 1);
+    let change = &diff.modified[0];
+    assert_eq!(change.instrument_token(), "13368066");
+    assert!(change.changed(InstrumentField::LotSize));
+    assert!(change.changed(InstrumentField::TickSize));
+    assert!(!change.changed(InstrumentField::Strike));
+    assert_eq!(change.changes[0].to_string(), "tick_size: 0.05 -> 0.1");
+    assert_eq!(change.changes[1].to_string(), "lot_size: 25 -> 75");
+
+    assert_eq!(diff.changes_to(InstrumentField::LotSize).count(), 1);
+    assert_eq!(diff.changes_to(InstrumentField::Name).count(), 0);
+}
+
+#[test]
+fn test_identical_snapshots() {
+    let rows = "13368066,52219,NIFTY24DECFUT,NIFTY,0,2024-12-26,0,0.05,25,FUT,NFO-FUT,NFO\n";
+    let diff = InstrumentDiff::between(&master(rows), &master(rows));
+    assert!(diff.is_empty());
+
+    let diff = InstrumentDiff::between(&InstrumentMaster::default(), &master(rows));
+    assert_eq!(diff.added.len(), 1);
+    assert!(diff.removed.is_empty());
+}
@@ -0,0 +1,96 @@
+use project_models::data_models::instrument_data::{BaseExchange, Exchange, InstrumentType, Segment};
+use project_models::data_models::{kite_str, ParseEnumError};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+/// Checks every discriminant in `0..count` round-trips through `TryFrom<i8>`, `Display` and `FromStr`.
+fn assert_round_trips<T>(count: i8)
+where
+    T: TryFrom<i8, Error = ParseEnumError> + FromStr<Err = ParseEnumError> + ToString + PartialEq + Debug,
+{
+    for i in 0..count {
+        let value = T::try_from(i).unwrap();
+        assert_eq!(T::from_str(&value.to_string()).unwrap(), value);
+    }
+    assert!(T::try_from(count).is_err());
+    assert!(T::try_from(-1).is_err());
+}
+
+#[test]
+fn test_all_variants_round_trip() {
+    assert_round_trips::<Exchange>(10);
+    assert_round_trips::<Segment>(16);
+    assert_round_trips::<InstrumentType>(4);
+    assert_round_trips::<BaseExchange>(5);
+}
+
+#[test]
+fn test_kite_wire_names() {
+    assert_eq!(Exchange::Nfo.to_string(), "NFO");
+    assert_eq!(Segment::NfoOpt.to_string(), "NFO-OPT");
+    assert_eq!(InstrumentType::Ce.to_string(), "CE");
+    assert_eq!(BaseExchange::Nseix.to_string(), "NSEIX");
+
+    assert_eq!("BCD-FUT".parse::<Segment>(), Ok(Segment::BcdFut));
+    assert_eq!(Segment::try_from(14), Ok(Segment::NfoOpt));
+}
+
+#[test]
+fn test_parse_errors() {
+    let err = "nfo".parse::<Exchange>().unwrap_err();
+    assert_eq!(err.to_string(), "Invalid exchange value: nfo");
+
+    let err = InstrumentType::try_from(9).unwrap_err();
+    assert_eq!(err.to_string(), "Invalid instrument type value: 9");
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct QuoteKey {
+    #[serde(with = "kite_str")]
+    exchange: Exchange,
+    #[serde(with = "kite_str")]
+    segment: Segment,
+    #[serde(with = "kite_str::option")]
+    instrument_type: Option<InstrumentType>,
+    #[serde(with = "kite_str::vec")]
+    base_exchanges: Vec<BaseExchange>,
+}
+
+#[test]
+fn test_string_serde_is_opt_in() {
+    let key = QuoteKey {
+        exchange: Exchange::Nfo,
+        segment: Segment::NfoOpt,
+        instrument_type: Some(InstrumentType::Pe),
+        base_exchanges: vec![BaseExchange::Nse, BaseExchange::Bse],
+    };
+
+    let json = serde_json::to_value(&key).unwrap();
+    assert_eq!(
+        json,
+        json!({
+            "exchange": "NFO",
+            "segment": "NFO-OPT",
+            "instrument_type": "PE",
+            "base_exchanges": ["NSE", "BSE"]
+        })
+    );
+    assert_eq!(serde_json::from_value::<QuoteKey>(json).unwrap(), key);
+
+    // Without the attribute the ClickHouse integer encoding is kept.
+    assert_eq!(serde_json::to_value(Exchange::Nfo).unwrap(), json!(6));
+    assert_eq!(serde_json::from_value::<Segment>(json!(14)).unwrap(), Segment::NfoOpt);
+}
+
+#[test]
+fn test_string_serde_rejects_unknown_names() {
+    let result = serde_json::from_value::<QuoteKey>(json!({
+        "exchange": "NYSE",
+        "segment": "NFO-OPT",
+        "instrument_type": null,
+        "base_exchanges": []
+    }));
+    assert!(result.unwrap_err().to_string().contains("Invalid exchange value: NYSE"));
+}
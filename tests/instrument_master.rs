@@ -0,0 +1,83 @@
+use chrono::NaiveDate;
+use project_models::data_models::instrument_data::{Exchange, InstrumentCsvReader, InstrumentMaster, InstrumentType};
+
+const DUMP: &str = "\
+instrument_token,exchange_token,tradingsymbol,name,last_price,expiry,strike,tick_size,lot_size,instrument_type,segment,exchange
+256265,1001,NIFTY 50,NIFTY 50,0,,0,0,0,EQ,INDICES,NSE
+13368066,52219,NIFTY24DECFUT,NIFTY,0,2024-12-26,0,0.05,25,FUT,NFO-FUT,NFO
+13369090,52223,NIFTY25JANFUT,NIFTY,0,2025-01-30,0,0.05,25,FUT,NFO-FUT,NFO
+10923010,42668,NIFTY24D1924000CE,NIFTY,0,2024-12-19,24000,0.05,25,CE,NFO-OPT,NFO
+10923266,42669,NIFTY24D1924000PE,NIFTY,0,2024-12-19,24000,0.05,25,PE,NFO-OPT,NFO
+260105,1016,NIFTY BANK,NIFTY BANK,0,,0,0,0,EQ,INDICES,NSE
+1001,1001,SENSEX,SENSEX,0,,0,0,0,EQ,INDICES,BSE
+";
+
+fn master() -> InstrumentMaster {
+    InstrumentCsvReader::new(DUMP.as_bytes())
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+}
+
+#[test]
+fn test_point_lookups() {
+    let master = master();
+    assert_eq!(master.len(), 7);
+
+    assert_eq!(master.by_token("13368066").unwrap().tradingsymbol, "NIFTY24DECFUT");
+    assert_eq!(
+        master.by_symbol(Exchange::Nfo, "NIFTY24D1924000PE").unwrap().instrument_type,
+        InstrumentType::Pe
+    );
+    assert!(master.by_symbol(Exchange::Nse, "NIFTY24DECFUT").is_none());
+
+    // The same exchange token is used on NSE and BSE.
+    assert_eq!(master.by_exchange_token(Exchange::Nse, "1001").unwrap().instrument_token, "256265");
+    assert_eq!(master.by_exchange_token(Exchange::Bse, "1001").unwrap().tradingsymbol, "SENSEX");
+}
+
+#[test]
+fn test_grouped_queries() {
+    let master = master();
+
+    // Index rows are aliased to NIFTY, so they group with the derivatives.
+    assert_eq!(master.by_name("NIFTY").count(), 5);
+    assert_eq!(master.by_name("BANKNIFTY").count(), 1);
+    assert_eq!(master.by_name("FINNIFTY").count(), 0);
+
+    let dec19 = NaiveDate::from_ymd_opt(2024, 12, 19).unwrap();
+    assert_eq!(master.by_expiry("NIFTY", dec19).count(), 2);
+    assert_eq!(master.by_type("NIFTY", InstrumentType::Fut).count(), 2);
+    assert_eq!(
+        master.expiries("NIFTY"),
+        vec![
+            dec19,
+            NaiveDate::from_ymd_opt(2024, 12, 26).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 1, 30).unwrap(),
+        ]
+    );
+}
+
+#[test]
+fn test_duplicate_tokens_keep_last_row() {
+    let mut instruments: Vec<_> = InstrumentCsvReader::new(DUMP.as_bytes())
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    let mut updated = instruments[1].clone();
+    updated.last_price = 24100.0;
+    instruments.push(updated);
+
+    let master = InstrumentMaster::new(instruments);
+    assert_eq!(master.len(), 7);
+    assert_eq!(master.by_token("13368066").unwrap().last_price, 24100.0);
+}
+
+#[test]
+fn test_clones_share_the_index() {
+    let master = master();
+    let shared = master.clone();
+    let handle = std::thread::spawn(move || shared.by_token("260105").map(|i| i.tradingsymbol.clone()));
+    assert_eq!(handle.join().unwrap().as_deref(), Some("NIFTY BANK"));
+    assert_eq!(master.len(), 7);
+}
@@ -0,0 +1,63 @@
+use chrono::NaiveDate;
+use clickhouse::Row;
+use project_models::data_models::instrument_data::queries::{
+    create_instrument_snapshots_table, delete_snapshot, select_instrument_history, select_instruments_as_of,
+};
+use project_models::data_models::instrument_data::{Instrument, InstrumentCsvReader, InstrumentSnapshot};
+use project_models::data_models::SchemaTarget;
+
+const DUMP: &str = "\
+instrument_token,exchange_token,tradingsymbol,name,last_price,expiry,strike,tick_size,lot_size,instrument_type,segment,exchange
+10923010,42668,NIFTY24D1924000CE,NIFTY,0,2024-12-19,24000,0.05,25,CE,NFO-OPT,NFO
+";
+
+/// Column names in the order they appear in a `CREATE TABLE` statement.
+fn columns(create: &str) -> Vec<&str> {
+    let body = &create[create.find('(').unwrap() + 1..create.rfind("ENGINE").unwrap()];
+    let mut depth = 0;
+    let mut columns = Vec::new();
+    for line in body.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        if depth == 0 && line != ")" {
+            columns.push(line.split_whitespace().next().unwrap());
+        }
+        depth += line.matches('(').count() as i32 - line.matches(')').count() as i32;
+    }
+    columns
+}
+
+#[test]
+fn test_row_matches_table_columns() {
+    let create = create_instrument_snapshots_table(&SchemaTarget::new("data"));
+    assert_eq!(columns(&create), InstrumentSnapshot::COLUMN_NAMES);
+    assert!(create.contains("ReplacingMergeTree"));
+    assert!(create.contains("ORDER BY (snapshot_date, instrument_token)"));
+}
+
+#[test]
+fn test_snapshot_columns_follow_instrument() {
+    let mut expected = vec!["snapshot_date"];
+    expected.extend_from_slice(Instrument::COLUMN_NAMES);
+    assert_eq!(InstrumentSnapshot::COLUMN_NAMES, expected.as_slice());
+}
+
+#[test]
+fn test_snapshot_round_trip() {
+    let instrument: Instrument = InstrumentCsvReader::new(DUMP.as_bytes()).unwrap().next().unwrap().unwrap();
+    let date = NaiveDate::from_ymd_opt(2024, 12, 16).unwrap();
+
+    let snapshot = InstrumentSnapshot::new(date, instrument.clone());
+    assert_eq!(snapshot.snapshot_date, date);
+    assert_eq!(snapshot.lot_size, 25);
+    assert_eq!(Instrument::from(snapshot), instrument);
+}
+
+#[test]
+fn test_as_of_queries_take_bind_parameters() {
+    let target = SchemaTarget::new("data");
+    let as_of = select_instruments_as_of(&target);
+    assert!(as_of.starts_with("\nSELECT ?fields\n"));
+    assert_eq!(as_of.replace("?fields", "").matches('?').count(), 1);
+    assert!(as_of.contains("FROM data.instrument_snapshots FINAL"));
+    assert!(select_instrument_history(&target).contains("ORDER BY snapshot_date"));
+    assert!(delete_snapshot(&target).contains("ALTER TABLE data.instrument_snapshots DELETE"));
+}
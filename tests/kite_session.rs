@@ -0,0 +1,125 @@
+use mockito::Matcher;
+use project_models::config_models::{AccountRole, ZerodhaConfig};
+use project_models::data_models::generic_data::{KiteSession, SessionError};
+
+fn zerodha_config() -> ZerodhaConfig {
+    ZerodhaConfig {
+        account_id: "research".to_string(),
+        api_key: "test_key".to_string(),
+        api_secret: "test_secret".to_string(),
+        user_name: "test_user".to_string(),
+        role: AccountRole::Data,
+    }
+}
+
+const SESSION_RESPONSE: &str = r#"{
+    "status": "success",
+    "data": {
+        "user_type": "individual",
+        "email": "xxxyyy@gmail.com",
+        "user_name": "Kite Connect",
+        "user_shortname": "Connect",
+        "broker": "ZERODHA",
+        "exchanges": ["NSE", "NFO", "BFO", "CDS", "BSE", "MCX", "BCD", "MF"],
+        "products": ["CNC", "NRML", "MIS", "BO", "CO"],
+        "order_types": ["MARKET", "LIMIT", "SL", "SL-M"],
+        "avatar_url": null,
+        "user_id": "XX0000",
+        "api_key": "test_key",
+        "access_token": "access_abc",
+        "public_token": "public_abc",
+        "enctoken": "enc_abc",
+        "refresh_token": "",
+        "silo": "",
+        "login_time": "2021-01-01 16:15:14",
+        "meta": { "demat_consent": "physical" }
+    }
+}"#;
+
+#[test]
+fn test_login_url() {
+    let session = KiteSession::new(&zerodha_config());
+    assert_eq!(
+        session.login_url(),
+        "https://kite.zerodha.com/connect/login?v=3&api_key=test_key"
+    );
+
+    let session = session.with_login_url("http://localhost:1234/connect/login/");
+    assert_eq!(session.login_url(), "http://localhost:1234/connect/login?v=3&api_key=test_key");
+}
+
+#[test]
+fn test_checksum() {
+    let session = KiteSession::new(&zerodha_config());
+    // sha256("test_key" + "req_token" + "test_secret")
+    assert_eq!(
+        session.checksum("req_token"),
+        "78a41c9e18d99031e221863b4f3b581efb0a39fd1591d71cf422075a1dee147d"
+    );
+}
+
+#[test]
+fn test_token_request() {
+    let session = KiteSession::new(&zerodha_config()).with_api_url("http://localhost:1234/");
+    let request = session.token_request("req_token");
+
+    assert_eq!(request.url, "http://localhost:1234/session/token");
+    assert_eq!(request.params[0], ("api_key".to_string(), "test_key".to_string()));
+    assert_eq!(request.params[1], ("request_token".to_string(), "req_token".to_string()));
+    assert_eq!(request.params[2], ("checksum".to_string(), session.checksum("req_token")));
+}
+
+#[tokio::test]
+async fn test_generate_session_against_mock_server() {
+    let mut server = mockito::Server::new_async().await;
+    let session = KiteSession::new(&zerodha_config()).with_api_url(server.url());
+
+    let mock = server
+        .mock("POST", "/session/token")
+        .match_header("X-Kite-Version", "3")
+        .match_body(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("api_key".into(), "test_key".into()),
+            Matcher::UrlEncoded("request_token".into(), "req_token".into()),
+            Matcher::UrlEncoded("checksum".into(), session.checksum("req_token")),
+        ]))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(SESSION_RESPONSE)
+        .create_async()
+        .await;
+
+    let user = session.generate_session("req_token").await.unwrap();
+    mock.assert_async().await;
+
+    assert_eq!(user.user_id, "XX0000");
+    assert_eq!(user.access_token, "access_abc");
+    assert_eq!(user.avatar_url, None);
+    assert_eq!(user.login_time.to_string(), "2021-01-01 16:15:14.0");
+}
+
+#[tokio::test]
+async fn test_generate_session_api_error() {
+    let mut server = mockito::Server::new_async().await;
+    let session = KiteSession::new(&zerodha_config()).with_api_url(server.url());
+
+    server
+        .mock("POST", "/session/token")
+        .with_status(403)
+        .with_body(r#"{"status": "error", "message": "Token is invalid or has expired.", "data": null, "error_type": "TokenException"}"#)
+        .create_async()
+        .await;
+
+    match session.generate_session("stale_token").await {
+        Err(SessionError::Api { error_type, message }) => {
+            assert_eq!(error_type, "TokenException");
+            assert_eq!(message, "Token is invalid or has expired.");
+        }
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_session_response_rejects_non_json() {
+    let result = KiteSession::parse_session_response("<html>Bad Gateway</html>");
+    assert!(matches!(result, Err(SessionError::InvalidResponse(_))));
+}
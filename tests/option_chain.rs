@@ -0,0 +1,87 @@
+use chrono::NaiveDate;
+use project_models::data_models::instrument_data::{
+    InstrumentCsvReader, InstrumentMaster, InstrumentType, OptionChain, StrikeRow,
+};
+
+const DUMP: &str = "\
+instrument_token,exchange_token,tradingsymbol,name,last_price,expiry,strike,tick_size,lot_size,instrument_type,segment,exchange
+13368066,52219,NIFTY24DECFUT,NIFTY,0,2024-12-26,0,0.05,25,FUT,NFO-FUT,NFO
+1,1,NIFTY24D1923900CE,NIFTY,0,2024-12-19,23900,0.05,25,CE,NFO-OPT,NFO
+2,2,NIFTY24D1923900PE,NIFTY,0,2024-12-19,23900,0.05,25,PE,NFO-OPT,NFO
+3,3,NIFTY24D1924000PE,NIFTY,0,2024-12-19,24000,0.05,25,PE,NFO-OPT,NFO
+4,4,NIFTY24D1924000CE,NIFTY,0,2024-12-19,24000,0.05,25,CE,NFO-OPT,NFO
+5,5,NIFTY24D1924100CE,NIFTY,0,2024-12-19,24100,0.05,25,CE,NFO-OPT,NFO
+6,6,NIFTY24D1924200CE,NIFTY,0,2024-12-19,24200,0.05,25,CE,NFO-OPT,NFO
+7,7,NIFTY24D1924200PE,NIFTY,0,2024-12-19,24200,0.05,25,PE,NFO-OPT,NFO
+8,8,NIFTY24DEC24000CE,NIFTY,0,2024-12-26,24000,0.05,25,CE,NFO-OPT,NFO
+9,9,BANKNIFTY24DEC52000CE,BANKNIFTY,0,2024-12-24,52000,0.05,15,CE,NFO-OPT,NFO
+";
+
+fn date(m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, m, d).unwrap()
+}
+
+fn master() -> InstrumentMaster {
+    InstrumentCsvReader::new(DUMP.as_bytes())
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+}
+
+#[test]
+fn test_chain_pairs_calls_and_puts() {
+    let chain = master().option_chain("NIFTY", date(12, 19)).unwrap();
+
+    assert_eq!(chain.name(), "NIFTY");
+    assert_eq!(chain.expiry(), date(12, 19));
+    assert_eq!(chain.lot_size(), 25);
+    assert_eq!(chain.tick_size(), 0.05);
+    assert_eq!(chain.strikes(), vec![23900.0, 24000.0, 24100.0, 24200.0]);
+
+    assert_eq!(chain.call(24000.0).unwrap().tradingsymbol, "NIFTY24D1924000CE");
+    assert_eq!(chain.put(24000.0).unwrap().tradingsymbol, "NIFTY24D1924000PE");
+    assert!(chain.row(24100.0).unwrap().put.is_none());
+    assert!(chain.row(24050.0).is_none());
+}
+
+#[test]
+fn test_atm_strike() {
+    let chain = master().option_chain("NIFTY", date(12, 19)).unwrap();
+
+    assert_eq!(chain.atm_strike(24030.0), Some(24000.0));
+    assert_eq!(chain.atm_strike(24070.0), Some(24100.0));
+    assert_eq!(chain.atm_strike(24050.0), Some(24000.0));
+    assert_eq!(chain.atm_strike(10000.0), Some(23900.0));
+    assert_eq!(chain.atm_strike(99999.0), Some(24200.0));
+}
+
+#[test]
+fn test_strikes_around_atm() {
+    let chain = master().option_chain("NIFTY", date(12, 19)).unwrap();
+
+    let strikes = |rows: &[StrikeRow]| {
+        rows.iter().map(|r| r.strike).collect::<Vec<_>>()
+    };
+    assert_eq!(strikes(chain.around_atm(24100.0, 1)), vec![24000.0, 24100.0, 24200.0]);
+    assert_eq!(strikes(chain.around_atm(23900.0, 2)), vec![23900.0, 24000.0, 24100.0]);
+    assert_eq!(strikes(chain.around_atm(24000.0, 0)), vec![24000.0]);
+    assert_eq!(chain.ladder().around_atm(24100.0, 1), [24000.0, 24100.0, 24200.0]);
+}
+
+#[test]
+fn test_next_option_expiry() {
+    let master = master();
+    assert_eq!(master.next_option_expiry("NIFTY", date(12, 1)), Some(date(12, 19)));
+    assert_eq!(master.next_option_expiry("NIFTY", date(12, 20)), Some(date(12, 26)));
+    assert_eq!(master.next_option_expiry("NIFTY", date(12, 27)), None);
+
+    let next = master.next_option_expiry("BANKNIFTY", date(12, 1)).unwrap();
+    assert_eq!(master.option_chain("BANKNIFTY", next).unwrap().lot_size(), 15);
+}
+
+#[test]
+fn test_no_options_means_no_chain() {
+    let master = master();
+    assert!(master.option_chain("FINNIFTY", date(12, 19)).is_none());
+    assert!(OptionChain::new("NIFTY", date(12, 26), master.by_type("NIFTY", InstrumentType::Fut)).is_none());
+}
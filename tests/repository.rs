@@ -0,0 +1,114 @@
+use chrono::{TimeZone, Utc};
+use project_models::data_models::futures_data::FuturesData;
+use project_models::data_models::hist_data::{HistData, Interval};
+use project_models::data_models::instrument_data::{Exchange, Instrument, InstrumentCsvReader};
+use project_models::data_models::repository::{
+    FuturesRepo, HistDataRepo, InstrumentRepo, RepoError, TableRepo, DEFAULT_BATCH_SIZE,
+};
+use std::error::Error;
+use project_models::data_models::SchemaTarget;
+
+mod common;
+use common::{date, TestDatabase};
+
+const DUMP: &str = "\
+instrument_token,exchange_token,tradingsymbol,name,last_price,expiry,strike,tick_size,lot_size,instrument_type,segment,exchange
+256265,1001,NIFTY 50,NIFTY 50,0,,0,0,0,EQ,INDICES,NSE
+13238786,51714,NIFTY24DECFUT,NIFTY,0,2024-12-26,0,0.05,25,FUT,NFO-FUT,NFO
+10923010,42668,NIFTY24D1924000CE,NIFTY,0,2024-12-19,24000,0.05,25,CE,NFO-OPT,NFO
+";
+
+fn instruments() -> Vec<Instrument> {
+    InstrumentCsvReader::new(DUMP.as_bytes()).unwrap().map(Result::unwrap).collect()
+}
+
+#[test]
+fn test_repos_use_target_tables() {
+    let target = SchemaTarget::new("scratch").with_prefix("run_1_");
+
+    let instruments = InstrumentRepo::new(clickhouse::Client::default(), target.clone());
+    assert_eq!(instruments.instruments().table(), "scratch.run_1_instruments");
+    assert_eq!(instruments.snapshots().table(), "scratch.run_1_instrument_snapshots");
+
+    let futures = FuturesRepo::new(clickhouse::Client::default(), target.clone());
+    assert_eq!(futures.futures().table(), "scratch.run_1_futures");
+    assert_eq!(futures.staging().table(), "scratch.run_1_futures_staging");
+
+    let hist = HistDataRepo::new(clickhouse::Client::default(), target);
+    assert_eq!(hist.candles().table(), "scratch.run_1_historical_data");
+    assert_eq!(hist.max_datetimes().table(), "scratch.run_1_historical_data_max_datetime");
+}
+
+#[test]
+fn test_batch_size() {
+    let target = SchemaTarget::new("data");
+    let repo: TableRepo<HistData> = TableRepo::new(clickhouse::Client::default(), &target);
+    assert_eq!(repo.batch_size(), DEFAULT_BATCH_SIZE);
+    assert_eq!(repo.clone().with_batch_size(500).batch_size(), 500);
+    assert_eq!(repo.with_batch_size(0).batch_size(), 1);
+
+    let futures = FuturesRepo::new(clickhouse::Client::default(), target).with_batch_size(2);
+    assert_eq!(futures.futures().batch_size(), 2);
+    assert_eq!(futures.staging().batch_size(), 2);
+}
+
+#[test]
+fn test_clickhouse_error_is_the_source() {
+    let err = RepoError::from(clickhouse::error::Error::RowNotFound);
+    assert!(matches!(err, RepoError::ClickHouse(clickhouse::error::Error::RowNotFound)));
+    let source = err.source().unwrap();
+    assert_eq!(err.to_string(), format!("ClickHouse error: {}", source));
+}
+
+/// Needs a local server: `CLICKHOUSE_URL=http://localhost:8123 cargo test --test repository -- --ignored`
+#[tokio::test]
+#[ignore]
+async fn test_live_round_trip() {
+    let db = TestDatabase::migrated("repository").await;
+    let (client, target) = (&db.client, &db.target);
+
+    // A batch size of 2 splits the three instruments over two inserts.
+    let repo = InstrumentRepo::new(client.clone(), target.clone()).with_batch_size(2);
+    assert_eq!(repo.replace_all(&instruments()).await.unwrap(), 3);
+    assert_eq!(repo.instruments().count().await.unwrap(), 3);
+    let fut = repo.by_symbol(Exchange::Nfo, "NIFTY24DECFUT").await.unwrap().unwrap();
+    assert_eq!(fut.instrument_token, "13238786");
+    assert_eq!(repo.by_token("10923010").await.unwrap().unwrap().strike, 24000.0);
+    // The "NIFTY 50" index row is named "NIFTY" through the built-in alias.
+    assert_eq!(repo.by_name("NIFTY").await.unwrap().len(), 3);
+    let dec = date(2024, 12, 1);
+    let expiring = repo.expiring_between(dec, date(2024, 12, 20)).await.unwrap();
+    assert_eq!(expiring.len(), 1);
+
+    repo.insert_snapshot(dec, &instruments()).await.unwrap();
+    assert_eq!(repo.as_of(date(2024, 12, 5)).await.unwrap().len(), 3);
+    assert!(repo.as_of(date(2024, 11, 30)).await.unwrap().is_empty());
+
+    // The December future has no options listed; as a liquid future it needs none.
+    let futures = FuturesRepo::new(client.clone(), target.clone());
+    futures.rebuild_staging().await.unwrap();
+    let promotion = futures.promote_staging().await.unwrap();
+    assert_eq!(promotion.previous_rows, 0);
+    assert_eq!(promotion.diff.added.len(), promotion.promoted_rows);
+    let nifty: Vec<FuturesData> = futures.by_name(fut.base_exchange, "NIFTY").await.unwrap();
+    assert!(!nifty.is_empty());
+
+    let hist = HistDataRepo::new(client.clone(), target.clone());
+    let at = |minute: u32| Utc.with_ymd_and_hms(2024, 12, 2, 3, 45 + minute, 0).unwrap();
+    let candles: Vec<HistData> = (0..3)
+        .map(|minute| HistData {
+            tradingsymbol: "NIFTY24DECFUT".to_string(),
+            open: 1.0,
+            high: 2.0,
+            low: 0.5,
+            close: 1.5,
+            volume: 100,
+            datetime: at(minute),
+            interval: Interval::Minute,
+        })
+        .collect();
+    hist.insert(&candles).await.unwrap();
+    let range = hist.range("NIFTY24DECFUT", Interval::Minute, at(1), at(3)).await.unwrap();
+    assert_eq!(range.iter().map(|c| c.datetime).collect::<Vec<_>>(), vec![at(1), at(2)]);
+    assert!(hist.max_datetime("13238786", Interval::Minute).await.unwrap().is_none());
+}
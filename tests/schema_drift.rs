@@ -0,0 +1,170 @@
+use project_models::data_models::futures_data::FuturesData;
+use project_models::data_models::hist_data::HistData;
+use project_models::data_models::instrument_data::{Instrument, InstrumentSnapshot};
+use project_models::data_models::migrations::{
+    check_schema_drift, compare_table, expected_tables, DriftIssue, ExpectedTable, LiveTable,
+};
+use project_models::data_models::SchemaTarget;
+
+mod common;
+use common::TestDatabase;
+
+fn target() -> SchemaTarget {
+    SchemaTarget::new("data")
+}
+
+/// What ClickHouse reports for a table created from `expected`.
+fn live(expected: &ExpectedTable) -> LiveTable {
+    LiveTable {
+        engine: expected.engine.trim_end_matches("()").to_string(),
+        partition_key: expected.partition_key.clone(),
+        sorting_key: expected.sorting_key.trim_matches(|c| c == '(' || c == ')').to_string(),
+        columns: expected.columns.clone(),
+    }
+}
+
+#[test]
+fn test_expected_tables() {
+    let names: Vec<String> = expected_tables(&target()).iter().map(ExpectedTable::full_name).collect();
+    assert_eq!(
+        names,
+        vec![
+            "data.instruments",
+            "data.instrument_snapshots",
+            "data.futures",
+            "data.futures_staging",
+            "data.historical_data",
+            "data.historical_data_max_datetime"
+        ]
+    );
+
+    let instruments = ExpectedTable::of::<Instrument>(&SchemaTarget::new("test_db").with_prefix("run_1_"));
+    assert_eq!(instruments.database, "test_db");
+    assert_eq!(instruments.name, "run_1_instruments");
+}
+
+#[test]
+fn test_matching_schema_has_no_drift() {
+    for expected in expected_tables(&target()) {
+        let drift = compare_table(&expected, Some(&live(&expected)));
+        assert!(drift.issues.is_empty(), "{}: {:?}", drift.table, drift.issues);
+    }
+}
+
+#[test]
+fn test_missing_table() {
+    let drift = compare_table(&ExpectedTable::of::<HistData>(&target()), None);
+    assert_eq!(drift.table, "data.historical_data");
+    assert_eq!(drift.issues, vec![DriftIssue::MissingTable]);
+}
+
+#[test]
+fn test_column_drift() {
+    let expected = ExpectedTable::of::<FuturesData>(&target());
+    let mut table = live(&expected);
+    // Created from an old constant: `add_to_base` was UInt8 and `strike` didn't exist yet.
+    table.columns.retain(|(name, _)| name != "strike");
+    for (name, ty) in table.columns.iter_mut() {
+        if name == "add_to_base" {
+            *ty = "UInt8".to_string();
+        }
+    }
+    table.columns.push(("legacy".to_string(), "String".to_string()));
+
+    let drift = compare_table(&expected, Some(&table));
+    assert_eq!(
+        drift.issues,
+        vec![
+            DriftIssue::TypeMismatch {
+                column: "add_to_base".to_string(),
+                expected: "Float64".to_string(),
+                actual: "UInt8".to_string(),
+            },
+            DriftIssue::MissingColumn {
+                column: "strike".to_string(),
+                expected: "Array(Float64)".to_string(),
+            },
+            DriftIssue::UnexpectedColumn {
+                column: "legacy".to_string(),
+                actual: "String".to_string(),
+            },
+        ]
+    );
+    assert_eq!(drift.issues[0].to_string(), "column add_to_base is UInt8, expected Float64");
+}
+
+#[test]
+fn test_enum_drift() {
+    let expected = ExpectedTable::of::<Instrument>(&target());
+    let mut table = live(&expected);
+    for (name, ty) in table.columns.iter_mut() {
+        if name == "base_exchange" {
+            // Older table without GLOBAL, and without spaces as ClickHouse may print it.
+            *ty = "Enum8('Bse'=0,'Mcx'=1,'Nse'=2,'Nseix'=3)".to_string();
+        }
+        if name == "instrument_type" {
+            *ty = ty.replace(' ', "");
+        }
+    }
+
+    let drift = compare_table(&expected, Some(&table));
+    assert_eq!(
+        drift.issues,
+        vec![DriftIssue::EnumMismatch {
+            column: "base_exchange".to_string(),
+            missing: vec!["'Global' = 4".to_string()],
+            unexpected: vec![],
+        }]
+    );
+}
+
+#[test]
+fn test_engine_and_sort_key_drift() {
+    let expected = ExpectedTable::of::<Instrument>(&target());
+    let mut table = live(&expected);
+    table.engine = "ReplacingMergeTree".to_string();
+    table.sorting_key = "exchange, instrument_token".to_string();
+
+    let issues = compare_table(&expected, Some(&table)).issues;
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0].to_string(), "engine is ReplacingMergeTree, expected MergeTree");
+    assert_eq!(issues[1].to_string(), "sort key is (exchange, instrument_token), expected (instrument_token)");
+}
+
+#[test]
+fn test_partition_key_drift() {
+    let expected = ExpectedTable::of::<InstrumentSnapshot>(&target());
+    assert_eq!(expected.partition_key, "toYYYYMM(snapshot_date)");
+    let mut table = live(&expected);
+    table.partition_key = String::new();
+
+    let issues = compare_table(&expected, Some(&table)).issues;
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].to_string(), "partition key is (), expected (toYYYYMM(snapshot_date))");
+
+    // Unpartitioned on both sides.
+    let expected = ExpectedTable::of::<FuturesData>(&target());
+    assert!(compare_table(&expected, Some(&live(&expected))).issues.is_empty());
+}
+
+/// Needs a local server: `CLICKHOUSE_URL=http://localhost:8123 cargo test --test schema_drift -- --ignored`
+#[tokio::test]
+#[ignore]
+async fn test_live_schema_after_migrations() {
+    let db = TestDatabase::migrated("schema_drift").await;
+    let (client, target) = (&db.client, &db.target);
+    let staging = target.table("futures_staging");
+
+    let report = check_schema_drift(client, target).await.unwrap();
+    assert!(report.is_clean(), "{}", report);
+
+    client
+        .query(&format!("ALTER TABLE {} ADD COLUMN IF NOT EXISTS drift_probe UInt8", staging))
+        .execute()
+        .await
+        .unwrap();
+    let report = check_schema_drift(client, target).await.unwrap();
+    assert_eq!(report.tables.len(), 1);
+    assert_eq!(report.tables[0].table, staging);
+    assert!(matches!(&report.tables[0].issues[..], [DriftIssue::UnexpectedColumn { .. }]));
+}
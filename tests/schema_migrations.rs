@@ -0,0 +1,190 @@
+use chrono::{TimeZone, Utc};
+use project_models::data_models::migrations::{
+    migration_status, pending_migrations, validate_order, AppliedMigration, Migration, MigrationError,
+    MigrationRunner, MigrationState, MIGRATIONS,
+};
+use project_models::data_models::futures_data::queries::{create_futures_staging_table, create_futures_table};
+use project_models::data_models::hist_data::{HistData, HistDataMaxDatetime};
+use project_models::data_models::instrument_data::{Instrument, InstrumentSnapshot};
+use project_models::data_models::{ClickHouseTable, SchemaTarget};
+use std::error::Error;
+
+mod common;
+use common::TestDatabase;
+
+const MIGRATION_1: Migration = Migration::new(1, "create_a", "CREATE TABLE a (x UInt8) ENGINE = Memory;");
+const MIGRATION_2: Migration = Migration::new(
+    2,
+    "create_b_and_c",
+    "CREATE TABLE b (x UInt8) ENGINE = Memory;\n\nCREATE TABLE c (x UInt8) ENGINE = Memory;\n",
+);
+
+fn applied(migration: &Migration) -> AppliedMigration {
+    AppliedMigration {
+        version: migration.version,
+        name: migration.name.to_string(),
+        checksum: migration.checksum(),
+        applied_at: Utc.with_ymd_and_hms(2024, 12, 20, 3, 30, 0).unwrap(),
+    }
+}
+
+#[test]
+fn test_embedded_migrations() {
+    validate_order(MIGRATIONS).unwrap();
+    assert_eq!(MIGRATIONS[0].version, 1);
+
+    let target = SchemaTarget::new("test_db").with_prefix("run_1_");
+    for migration in MIGRATIONS {
+        assert!(!migration.statements(&target).is_empty(), "{} is empty", migration.name);
+        for statement in migration.statements(&target) {
+            assert!(statement.contains(" test_db.run_1_"), "{}", statement);
+            assert!(!statement.contains("${"), "{}", statement);
+        }
+    }
+    assert_eq!(MIGRATIONS[2].statements(&target).len(), 2);
+    let runner = MigrationRunner::new(clickhouse::Client::default().with_url("http://localhost:8123"), target.clone());
+    assert_eq!(runner.migrations(), MIGRATIONS);
+    assert_eq!(runner.target(), &target);
+}
+
+/// The create migrations are the DDL the row types generate, less any later migration's changes.
+#[test]
+fn test_create_migrations_match_row_types() {
+    let target = SchemaTarget::new("test_db").with_prefix("run_1_");
+    assert_eq!(MIGRATIONS[0].statements(&target), vec![Instrument::create_table_in(&target)]);
+    assert_eq!(MIGRATIONS[1].statements(&target), vec![InstrumentSnapshot::create_table_in(&target)]);
+    assert_eq!(
+        MIGRATIONS[3].statements(&target),
+        vec![HistData::create_table_in(&target), HistDataMaxDatetime::create_table_in(&target)]
+    );
+
+    // 0005 later made `base_expiry` Nullable; undo that to compare with what 0003 created.
+    let before_0005 = |sql: String| sql.replace("base_expiry Nullable(Date)", "base_expiry Date");
+    assert_eq!(
+        MIGRATIONS[2].statements(&target),
+        vec![
+            before_0005(create_futures_table(&target)),
+            before_0005(create_futures_staging_table(&target)),
+        ]
+    );
+    assert!(MIGRATIONS[4]
+        .statements(&target)
+        .contains(&"ALTER TABLE test_db.run_1_futures MODIFY COLUMN base_expiry Nullable(Date)".to_string()));
+}
+
+#[test]
+fn test_statements_and_checksum() {
+    assert_eq!(
+        MIGRATION_2.statements(&SchemaTarget::new("data")),
+        vec!["CREATE TABLE b (x UInt8) ENGINE = Memory", "CREATE TABLE c (x UInt8) ENGINE = Memory"]
+    );
+    assert_eq!(MIGRATION_1.checksum().len(), 64);
+    assert_eq!(MIGRATION_1.checksum(), MIGRATION_1.clone().checksum());
+    assert_ne!(MIGRATION_1.checksum(), Migration::new(1, "create_a", "CREATE TABLE a (x UInt16) ENGINE = Memory;").checksum());
+}
+
+#[test]
+fn test_semicolons_in_literals_and_comments() {
+    let migration = Migration::new(
+        3,
+        "quoted",
+        "-- backfill; then index\n\
+         ALTER TABLE ${a} UPDATE note = 'a;b' WHERE note = 'it\\'s;' AND `odd;name` = 1;\n\
+         /* two; statements */ ALTER TABLE ${a} DELETE WHERE note = ';';\n\
+         -- trailing; comment\n",
+    );
+    assert_eq!(
+        migration.statements(&SchemaTarget::new("data")),
+        vec![
+            "-- backfill; then index\nALTER TABLE data.a UPDATE note = 'a;b' WHERE note = 'it\\'s;' AND `odd;name` = 1",
+            "/* two; statements */ ALTER TABLE data.a DELETE WHERE note = ';'",
+        ]
+    );
+}
+
+#[test]
+fn test_status() {
+    let orphan = AppliedMigration {
+        version: 7,
+        name: "from_a_newer_release".to_string(),
+        ..applied(&MIGRATION_1)
+    };
+    let statuses = migration_status(&[MIGRATION_1, MIGRATION_2], &[applied(&MIGRATION_1), orphan]);
+
+    assert_eq!(statuses.len(), 3);
+    assert!(matches!(statuses[0].state, MigrationState::Applied { .. }));
+    assert_eq!(statuses[1].state, MigrationState::Pending);
+    assert_eq!(statuses[2].state, MigrationState::Unknown);
+    assert_eq!(statuses[2].name, "from_a_newer_release");
+}
+
+#[test]
+fn test_pending_skips_applied() {
+    let migrations = [MIGRATION_1, MIGRATION_2];
+    assert_eq!(pending_migrations(&migrations, &[]).unwrap().len(), 2);
+
+    let pending = pending_migrations(&migrations, &[applied(&MIGRATION_1)]).unwrap();
+    assert_eq!(pending, vec![&MIGRATION_2]);
+}
+
+#[test]
+fn test_edited_migration_is_rejected() {
+    let edited = Migration::new(1, "create_a", "CREATE TABLE a (x UInt16) ENGINE = Memory;");
+    let err = pending_migrations(&[edited, MIGRATION_2], &[applied(&MIGRATION_1)]).unwrap_err();
+
+    assert!(matches!(err, MigrationError::ChecksumMismatch { version: 1, .. }));
+    assert_eq!(err.to_string(), "Migration 1 (create_a) was modified after it was applied");
+}
+
+#[test]
+fn test_out_of_order_migrations_are_rejected() {
+    assert!(matches!(
+        pending_migrations(&[MIGRATION_2, MIGRATION_1], &[]),
+        Err(MigrationError::InvalidOrder(1))
+    ));
+    assert!(matches!(validate_order(&[MIGRATION_1, MIGRATION_1]), Err(MigrationError::InvalidOrder(1))));
+}
+
+#[test]
+fn test_clickhouse_error_is_the_source() {
+    let err = MigrationError::from(clickhouse::error::Error::RowNotFound);
+    assert!(matches!(err, MigrationError::ClickHouse(clickhouse::error::Error::RowNotFound)));
+    let source = err.source().unwrap();
+    assert_eq!(err.to_string(), format!("ClickHouse error: {}", source));
+}
+
+#[test]
+fn test_bookkeeping_table() {
+    let sql = AppliedMigration::create_table_in(&SchemaTarget::new("data"));
+    assert!(sql.starts_with("CREATE TABLE IF NOT EXISTS data.schema_migrations\n"));
+    assert!(sql.contains("checksum String"));
+    assert!(sql.ends_with("ORDER BY (version)"));
+}
+
+/// Needs a local server: `CLICKHOUSE_URL=http://localhost:8123 cargo test --test schema_migrations -- --ignored`
+#[tokio::test]
+#[ignore]
+async fn test_live_dry_run_leaves_database_untouched() {
+    let db = TestDatabase::empty("schema_migrations").await;
+    let (client, target) = (&db.client, &db.target);
+    let tables = || async {
+        client
+            .query("SELECT count() FROM system.tables WHERE database = ?")
+            .bind(target.database())
+            .fetch_one::<u64>()
+            .await
+            .unwrap()
+    };
+
+    let runner = MigrationRunner::new(client.clone(), target.clone());
+    let pending = runner.dry_run().await.unwrap();
+    let statuses = runner.status().await.unwrap();
+    let tables_before_apply = tables().await;
+    runner.apply().await.unwrap();
+    let pending_after_apply = runner.dry_run().await.unwrap();
+
+    assert_eq!(pending, MIGRATIONS);
+    assert!(statuses.iter().all(|s| s.state == MigrationState::Pending));
+    assert_eq!(tables_before_apply, 0);
+    assert!(pending_after_apply.is_empty());
+}
@@ -0,0 +1,47 @@
+use project_models::config_models::ClickHouseConfig;
+use project_models::data_models::futures_data::queries::populate_future_staging;
+use project_models::data_models::instrument_data::Instrument;
+use project_models::data_models::{ClickHouseTable, SchemaTarget};
+
+fn config(database: &str) -> ClickHouseConfig {
+    ClickHouseConfig {
+        url: "http://localhost:8123".to_string(),
+        user: "default".to_string(),
+        password: None,
+        database: database.to_string(),
+    }
+}
+
+#[test]
+fn test_target_from_config() {
+    let target = SchemaTarget::from_config(&config("market"));
+    assert_eq!(target.database(), "market");
+    assert_eq!(target.prefix(), "");
+    assert_eq!(target.table("instruments"), "market.instruments");
+    assert_eq!(Instrument::TABLE, "instruments");
+    assert!(Instrument::create_table_in(&target).starts_with("CREATE TABLE IF NOT EXISTS market.instruments\n"));
+}
+
+#[test]
+fn test_prefix_isolates_tables() {
+    let target = SchemaTarget::from_config(&config("scratch")).with_prefix("run_42_");
+    assert_eq!(target.table_name("futures"), "run_42_futures");
+    assert_eq!(target.table("futures"), "scratch.run_42_futures");
+}
+
+#[test]
+fn test_render() {
+    let target = SchemaTarget::new("db").with_prefix("t_");
+    assert_eq!(target.render("SELECT * FROM ${a} JOIN ${b} ON 1"), "SELECT * FROM db.t_a JOIN db.t_b ON 1");
+    assert_eq!(target.render("SELECT '${unterminated'"), "SELECT '${unterminated'");
+    assert_eq!(target.render("SELECT 1"), "SELECT 1");
+}
+
+#[test]
+fn test_populate_qualifies_every_table() {
+    let sql = populate_future_staging(&SchemaTarget::new("market"));
+    assert!(sql.contains("INSERT INTO market.futures_staging"));
+    assert!(sql.contains("FROM market.instruments AS enriched"));
+    assert!(sql.contains("LEFT JOIN market.futures AS existing"));
+    assert!(!sql.contains("${"));
+}
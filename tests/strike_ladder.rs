@@ -0,0 +1,106 @@
+use project_models::data_models::strike_ladder::{Moneyness, OptionSide, StepSegment, StrikeLadder, TieBreak};
+
+mod common;
+use common::{date, future};
+
+fn ladder() -> StrikeLadder {
+    StrikeLadder::new(vec![24200.0, 24000.0, 24100.0, 23900.0, 24050.0, 24150.0, 24000.0])
+}
+
+#[test]
+fn test_new_sorts_and_dedups() {
+    assert_eq!(ladder().strikes(), [23900.0, 24000.0, 24050.0, 24100.0, 24150.0, 24200.0]);
+    assert_eq!(StrikeLadder::new(vec![f64::NAN, 100.0]).strikes(), [100.0]);
+    assert!(StrikeLadder::new(vec![]).is_empty());
+}
+
+#[test]
+fn test_nearest() {
+    let ladder = ladder();
+    assert_eq!(ladder.nearest(24060.0, TieBreak::Lower), Some(24050.0));
+    assert_eq!(ladder.nearest(24075.0, TieBreak::Lower), Some(24050.0));
+    assert_eq!(ladder.nearest(24075.0, TieBreak::Higher), Some(24100.0));
+    assert_eq!(ladder.atm(24075.0), Some(24050.0));
+    assert_eq!(ladder.atm(10.0), Some(23900.0));
+    assert_eq!(ladder.atm(99999.0), Some(24200.0));
+    assert_eq!(StrikeLadder::new(vec![]).atm(24000.0), None);
+
+    assert_eq!(ladder.nearest_k(24075.0, 3), vec![24000.0, 24050.0, 24100.0]);
+    assert_eq!(ladder.nearest_k(24000.0, 2), vec![24000.0, 24050.0]);
+    assert_eq!(ladder.nearest_k(24000.0, 100).len(), 6);
+    // 24000 and 24100 are both 50 from spot; the lower one is taken first.
+    assert_eq!(ladder.nearest_k(24050.0, 2), [24000.0, 24050.0]);
+    assert!(ladder.nearest_k(24050.0, 0).is_empty());
+    assert!(StrikeLadder::new(vec![]).nearest_k(24000.0, 3).is_empty());
+    assert_eq!(ladder.around_atm(24100.0, 1), [24050.0, 24100.0, 24150.0]);
+    assert_eq!(ladder.around_atm(23900.0, 2), [23900.0, 24000.0, 24050.0]);
+}
+
+#[test]
+fn test_steps() {
+    let uniform = StrikeLadder::new(vec![100.0, 102.5, 105.0, 107.5]);
+    assert_eq!(uniform.step(), Some(2.5));
+    assert!(uniform.is_uniform());
+
+    // 100-point strikes away from the money, 50-point near it.
+    let ladder = ladder();
+    assert_eq!(ladder.step(), Some(50.0));
+    assert!(!ladder.is_uniform());
+    assert_eq!(
+        ladder.step_segments(),
+        vec![
+            StepSegment { from: 23900.0, to: 24000.0, step: 100.0 },
+            StepSegment { from: 24000.0, to: 24200.0, step: 50.0 },
+        ]
+    );
+    assert_eq!(ladder.step_at(23900.0), Some(100.0));
+    assert_eq!(ladder.step_at(24200.0), Some(50.0));
+    assert_eq!(ladder.step_at(24025.0), None);
+
+    // Equally common gaps resolve to the smaller one.
+    assert_eq!(StrikeLadder::new(vec![0.0, 10.0, 15.0]).step(), Some(5.0));
+    assert_eq!(StrikeLadder::new(vec![0.1, 0.2, 0.3, 0.4]).step_segments().len(), 1);
+    assert_eq!(StrikeLadder::new(vec![100.0]).step(), None);
+}
+
+#[test]
+fn test_itm_and_otm() {
+    let ladder = ladder();
+    assert_eq!(ladder.itm(24050.0, OptionSide::Call), [23900.0, 24000.0]);
+    assert_eq!(ladder.otm(24050.0, OptionSide::Call), [24100.0, 24150.0, 24200.0]);
+    assert_eq!(ladder.itm(24050.0, OptionSide::Put), [24100.0, 24150.0, 24200.0]);
+    assert_eq!(ladder.otm(24060.0, OptionSide::Put), [23900.0, 24000.0, 24050.0]);
+}
+
+#[test]
+fn test_moneyness() {
+    let ladder = ladder();
+    let spot = 24060.0;
+    assert_eq!(ladder.moneyness(24050.0, spot, OptionSide::Call, 2), Some(Moneyness::Atm));
+    assert_eq!(ladder.moneyness(24000.0, spot, OptionSide::Call, 2), Some(Moneyness::Itm));
+    assert_eq!(ladder.moneyness(23900.0, spot, OptionSide::Call, 2), Some(Moneyness::DeepItm));
+    assert_eq!(ladder.moneyness(23900.0, spot, OptionSide::Put, 2), Some(Moneyness::DeepOtm));
+    assert_eq!(ladder.moneyness(24025.0, spot, OptionSide::Put, 2), None);
+
+    let buckets: Vec<Moneyness> = ladder.buckets(spot, OptionSide::Put, 2).into_iter().map(|(_, m)| m).collect();
+    assert_eq!(
+        buckets,
+        vec![
+            Moneyness::DeepOtm,
+            Moneyness::Otm,
+            Moneyness::Atm,
+            Moneyness::Itm,
+            Moneyness::DeepItm,
+            Moneyness::DeepItm,
+        ]
+    );
+}
+
+#[test]
+fn test_futures_data_ladder() {
+    let future = future("NIFTY", date(2024, 12, 26), vec![24000.0, 24050.0, 24100.0]);
+    let ladder = future.strike_ladder();
+    assert_eq!(ladder.len(), 3);
+    assert_eq!(ladder.step(), Some(50.0));
+    assert_eq!(ladder.atm(24080.0), Some(24100.0));
+}
@@ -0,0 +1,110 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use project_models::data_models::calendar_data::{CalendarError, MarketSession, SessionKind, TradingCalendar};
+use project_models::data_models::instrument_data::BaseExchange;
+use tempfile::TempDir;
+
+const CALENDAR: &str = r#"
+[NSE]
+holidays = ["2024-11-01", "2024-11-15", "2024-12-25"]
+
+[[NSE.special_sessions]]
+date = "2024-11-01"
+kind = "muhurat"
+open = "18:00"
+close = "19:00"
+
+[MCX]
+holidays = ["2024-12-25"]
+"#;
+
+fn date(m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, m, d).unwrap()
+}
+
+fn at(m: u32, d: u32, time: &str) -> NaiveDateTime {
+    date(m, d).and_time(chrono::NaiveTime::parse_from_str(time, "%H:%M").unwrap())
+}
+
+fn nse() -> TradingCalendar {
+    TradingCalendar::parse_all(CALENDAR).unwrap().remove(&BaseExchange::Nse).unwrap()
+}
+
+#[test]
+fn test_trading_days() {
+    let nse = nse();
+    assert!(nse.is_trading_day(date(12, 24)));
+    assert!(!nse.is_trading_day(date(12, 25)));
+    assert!(!nse.is_trading_day(date(12, 28)));
+    // Diwali is a holiday, but Muhurat trading makes it a trading day.
+    assert!(nse.is_trading_day(date(11, 1)));
+
+    // Mon 23rd → Mon 30th: 24, 26, 27, 30.
+    assert_eq!(nse.trading_days_between(date(12, 23), date(12, 30)), 4);
+    // Thu 31 Oct → Mon 4 Nov: the Muhurat day counts despite being a holiday.
+    assert_eq!(nse.trading_days_between(date(10, 31), date(11, 4)), 2);
+    assert_eq!(nse.trading_days_between(date(11, 4), date(10, 31)), 0);
+    assert_eq!(nse.previous_trading_day(date(12, 26)), Some(date(12, 24)));
+    assert_eq!(nse.previous_trading_day(date(12, 30)), Some(date(12, 27)));
+}
+
+#[test]
+fn test_market_hours() {
+    let nse = nse();
+    assert_eq!(nse.session_at(at(12, 24, "09:05")).unwrap().kind, SessionKind::PreOpen);
+    assert_eq!(nse.session_at(at(12, 24, "09:15")).unwrap().kind, SessionKind::Normal);
+    assert!(!nse.is_open(at(12, 24, "15:30")));
+    assert!(!nse.is_open(at(12, 25, "10:00")));
+
+    // Only the Muhurat session runs on Diwali.
+    assert!(!nse.is_open(at(11, 1, "10:00")));
+    assert_eq!(nse.session_at(at(11, 1, "18:30")).unwrap().kind, SessionKind::Muhurat);
+
+    let mcx = TradingCalendar::new(BaseExchange::Mcx);
+    assert_eq!(mcx.session_at(at(12, 24, "21:00")).unwrap().kind, SessionKind::Evening);
+}
+
+#[test]
+fn test_next_session_open() {
+    let nse = nse();
+    assert_eq!(nse.next_session_open(at(12, 24, "08:00")), Some(at(12, 24, "09:00")));
+    assert_eq!(nse.next_session_open(at(12, 24, "09:10")), Some(at(12, 24, "09:15")));
+    // Tue evening → skip Christmas → Thu pre-open.
+    assert_eq!(nse.next_session_open(at(12, 24, "16:00")), Some(at(12, 26, "09:00")));
+    assert_eq!(nse.next_session_open(at(11, 1, "09:00")), Some(at(11, 1, "18:00")));
+
+    assert_eq!(TradingCalendar::new(BaseExchange::Global).next_session_open(at(12, 24, "08:00")), None);
+}
+
+#[test]
+fn test_load_from_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("calendar.toml");
+    std::fs::write(&path, CALENDAR).unwrap();
+
+    let mcx = TradingCalendar::load(&path, BaseExchange::Mcx).unwrap();
+    assert!(mcx.holidays().is_holiday(date(12, 25)));
+
+    // No table for BSE: built-in timings, no holidays.
+    let bse = TradingCalendar::load(&path, BaseExchange::Bse).unwrap();
+    assert!(bse.is_trading_day(date(12, 25)));
+    assert_eq!(bse.sessions().len(), 2);
+
+    assert!(matches!(
+        TradingCalendar::load(temp_dir.path().join("missing.toml"), BaseExchange::Nse),
+        Err(CalendarError::Io(_))
+    ));
+}
+
+#[test]
+fn test_invalid_entries() {
+    assert_eq!(
+        TradingCalendar::parse_all("[NSE]\nholidays = [\"25-12-2024\"]").unwrap_err(),
+        CalendarError::InvalidDate("25-12-2024".to_string())
+    );
+    assert!(matches!(TradingCalendar::parse_all("[NYSE]\n"), Err(CalendarError::Parse(_))));
+    assert!(MarketSession::parse(SessionKind::Normal, "15:30", "09:15").is_err());
+
+    let custom = "[MCX]\nsessions = [{ kind = \"normal\", open = \"09:00\", close = \"23:55\" }]";
+    let mcx = TradingCalendar::parse_all(custom).unwrap().remove(&BaseExchange::Mcx).unwrap();
+    assert_eq!(mcx.sessions().len(), 1);
+}
@@ -0,0 +1,231 @@
+use project_models::data_models::generic_data::{OrderType, Product, User, UserError};
+use project_models::data_models::instrument_data::Exchange;
+use project_models::data_models::kite_str::KiteName;
+use redis::{FromRedisValue, ToRedisArgs, Value as RedisValue};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+fn session_json() -> Value {
+    json!({
+        "user_type": "individual",
+        "email": "xxxyyy@gmail.com",
+        "user_name": "Kite Connect",
+        "user_shortname": "Connect",
+        "broker": "ZERODHA",
+        "exchanges": ["NSE", "NFO", "BFO", "CDS", "BSE", "MCX", "BCD", "MF"],
+        "products": ["CNC", "NRML", "MIS", "BO", "CO"],
+        "order_types": ["MARKET", "LIMIT", "SL", "SL-M"],
+        "avatar_url": "https://s3.ap-south-1.amazonaws.com/zerodha-kite-blobs/avatar.png",
+        "user_id": "XX0000",
+        "api_key": "test_key",
+        "access_token": "access_abc",
+        "public_token": "public_abc",
+        "enctoken": "enc_abc",
+        "refresh_token": "",
+        "silo": "",
+        "login_time": "2021-01-01 16:15:14",
+        "meta": { "demat_consent": "physical" }
+    })
+}
+
+/// Converts the args written by `ToRedisArgs` into the reply `HGETALL` would return.
+fn as_hgetall_reply(user: &User) -> RedisValue {
+    RedisValue::Array(
+        user.to_redis_args()
+            .into_iter()
+            .map(RedisValue::BulkString)
+            .collect(),
+    )
+}
+
+/// The hash fields written by `ToRedisArgs`.
+fn redis_fields(user: &User) -> HashMap<String, String> {
+    user.to_redis_args()
+        .chunks(2)
+        .map(|pair| {
+            (
+                String::from_utf8(pair[0].clone()).unwrap(),
+                String::from_utf8(pair[1].clone()).unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_parse_full_session() {
+    let user = User::from_json(session_json()).unwrap();
+
+    assert_eq!(user.user_id, "XX0000");
+    assert_eq!(user.exchanges[0], KiteName::Known(Exchange::Nse));
+    assert_eq!(user.exchanges[7], KiteName::Other("MF".to_string()));
+    assert_eq!(
+        user.products,
+        vec![
+            KiteName::Known(Product::Cnc),
+            KiteName::Known(Product::Nrml),
+            KiteName::Known(Product::Mis),
+            KiteName::Other("BO".to_string()),
+            KiteName::Other("CO".to_string()),
+        ]
+    );
+    assert_eq!(user.order_types[3], KiteName::Known(OrderType::SlM));
+    assert_eq!(user.meta.demat_consent, "physical");
+    assert_eq!(
+        user.avatar_url.as_deref(),
+        Some("https://s3.ap-south-1.amazonaws.com/zerodha-kite-blobs/avatar.png")
+    );
+}
+
+#[test]
+fn test_optional_fields_default() {
+    let mut json = session_json();
+    let object = json.as_object_mut().unwrap();
+    object.remove("avatar_url");
+    object.remove("enctoken");
+    object.remove("refresh_token");
+    object.remove("meta");
+
+    let user = User::from_json(json).unwrap();
+    assert_eq!(user.avatar_url, None);
+    assert_eq!(user.enctoken, "");
+    assert_eq!(user.refresh_token, "");
+    assert_eq!(user.meta.demat_consent, "");
+}
+
+#[test]
+fn test_missing_field_is_an_error() {
+    let mut json = session_json();
+    json.as_object_mut().unwrap().remove("access_token");
+
+    match User::from_json(json) {
+        Err(UserError::Json { message, .. }) => assert!(message.contains("access_token")),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_wrong_type_reports_path() {
+    let mut json = session_json();
+    json["exchanges"][1] = json!(42);
+
+    match User::from_json(json) {
+        Err(UserError::Json { path, .. }) => assert_eq!(path, "exchanges[1]"),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_bad_login_time_is_an_error() {
+    let mut json = session_json();
+    json["login_time"] = json!("01/01/2021 16:15");
+
+    match User::from_json(json) {
+        Err(UserError::Json { path, .. }) => assert_eq!(path, "login_time"),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_redis_round_trip() {
+    let user = User::from_json(session_json()).unwrap();
+    let restored = User::from_redis_value(&as_hgetall_reply(&user)).unwrap();
+    assert_eq!(restored, user);
+}
+
+#[test]
+fn test_redis_round_trip_without_avatar() {
+    let mut user = User::from_json(session_json()).unwrap();
+    user.avatar_url = None;
+
+    let args: Vec<String> = user
+        .to_redis_args()
+        .into_iter()
+        .map(|arg| String::from_utf8(arg).unwrap())
+        .collect();
+    assert!(!args.contains(&"avatar_url".to_string()));
+    assert!(args.contains(&"2021-01-01 16:15:14".to_string()));
+
+    let restored = User::from_redis_value(&as_hgetall_reply(&user)).unwrap();
+    assert_eq!(restored, user);
+}
+
+#[test]
+fn test_redis_hash_with_invalid_json_field() {
+    let user = User::from_json(session_json()).unwrap();
+    let mut fields = redis_fields(&user);
+    fields.insert("products".to_string(), "CNC,MIS".to_string());
+
+    match User::from_redis_fields(&fields) {
+        Err(UserError::InvalidField { field, .. }) => assert_eq!(field, "products"),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_legacy_login_time_in_redis_hash() {
+    let user = User::from_json(session_json()).unwrap();
+    let mut fields = redis_fields(&user);
+
+    // Written by the old `ToRedisArgs`, which used `PrimitiveDateTime::to_string()`.
+    fields.insert("login_time".to_string(), user.login_time.to_string());
+    assert_eq!(fields["login_time"], "2021-01-01 16:15:14.0");
+    assert_eq!(User::from_redis_fields(&fields).unwrap(), user);
+
+    let mut json = session_json();
+    fields.insert("login_time".to_string(), "2021-01-01 9:05:00.0".to_string());
+    json["login_time"] = json!("2021-01-01 09:05:00");
+    assert_eq!(User::from_redis_fields(&fields).unwrap(), User::from_json(json).unwrap());
+}
+
+#[test]
+fn test_product_and_order_type_names() {
+    assert_eq!(OrderType::SlM.to_string(), "SL-M");
+    assert_eq!("NRML".parse::<Product>(), Ok(Product::Nrml));
+    assert_eq!("BO".parse::<Product>().unwrap_err().to_string(), "Invalid product value: BO");
+}
+
+#[test]
+fn test_empty_redis_hash_is_rejected() {
+    assert!(User::from_redis_value(&RedisValue::Array(vec![])).is_err());
+}
+
+#[test]
+fn test_trading_permissions() {
+    let mut json = session_json();
+    json["exchanges"] = json!(["NSE", "BSE"]);
+    json["products"] = json!(["CNC", "MIS"]);
+    let user = User::from_json(json).unwrap();
+
+    assert!(user.can_trade(Exchange::Nse, Product::Mis));
+    assert!(!user.can_trade(Exchange::Nfo, Product::Mis));
+    assert!(!user.can_trade(Exchange::Nse, Product::Nrml));
+    assert!(user.has_order_type(OrderType::SlM));
+}
+
+#[test]
+fn test_unknown_enum_values_are_kept() {
+    let mut json = session_json();
+    json["exchanges"] = json!(["NFO", "MF", "XYZ"]);
+    json["order_types"] = json!(["LIMIT", "ICEBERG"]);
+    let user = User::from_json(json).unwrap();
+
+    let known: Vec<&Exchange> = user.exchanges.iter().filter_map(KiteName::known).collect();
+    assert_eq!(known, vec![&Exchange::Nfo]);
+    assert_eq!(user.order_types[1], KiteName::Other("ICEBERG".to_string()));
+
+    let read_back = User::from_redis_value(&as_hgetall_reply(&user)).unwrap();
+    assert_eq!(read_back, user);
+}
+
+#[test]
+fn test_enum_lists_are_stored_as_kite_names() {
+    let user = User::from_json(session_json()).unwrap();
+    let args: Vec<String> = user
+        .to_redis_args()
+        .into_iter()
+        .map(|arg| String::from_utf8(arg).unwrap())
+        .collect();
+
+    assert!(args.contains(&r#"["MARKET","LIMIT","SL","SL-M"]"#.to_string()));
+    assert!(args.contains(&r#"["CNC","NRML","MIS","BO","CO"]"#.to_string()));
+}
@@ -0,0 +1,141 @@
+use project_models::config_models::RedisConfig;
+use project_models::data_models::generic_data::{
+    InMemorySessionStore, RedisSessionStore, SessionError, SessionStore, User,
+};
+use redis::Commands;
+use serde_json::json;
+use time::macros::{datetime, format_description, offset};
+use time::OffsetDateTime;
+
+fn user_logged_in_at(login_time: &str) -> User {
+    User::from_json(json!({
+        "user_type": "individual",
+        "email": "xxxyyy@gmail.com",
+        "user_name": "Kite Connect",
+        "user_shortname": "Connect",
+        "broker": "ZERODHA",
+        "exchanges": ["NSE", "NFO"],
+        "products": ["CNC", "MIS"],
+        "order_types": ["MARKET", "LIMIT"],
+        "avatar_url": null,
+        "user_id": "XX0000",
+        "api_key": "test_key",
+        "access_token": "access_abc",
+        "public_token": "public_abc",
+        "enctoken": "enc_abc",
+        "refresh_token": "",
+        "login_time": login_time,
+        "meta": { "demat_consent": "physical" }
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_login_time_is_ist() {
+    let user = user_logged_in_at("2024-03-04 09:10:00");
+    assert_eq!(user.login_time_ist(), datetime!(2024-03-04 09:10:00 +5:30));
+    assert_eq!(user.login_time_ist(), datetime!(2024-03-04 03:40:00 UTC));
+}
+
+#[test]
+fn test_expires_next_morning_after_daytime_login() {
+    let user = user_logged_in_at("2024-03-04 09:10:00");
+    assert_eq!(user.expires_at(), datetime!(2024-03-05 06:00:00 +5:30));
+}
+
+#[test]
+fn test_expires_same_morning_after_early_login() {
+    let user = user_logged_in_at("2024-03-04 05:30:00");
+    assert_eq!(user.expires_at(), datetime!(2024-03-04 06:00:00 +5:30));
+}
+
+#[test]
+fn test_is_expired() {
+    let user = user_logged_in_at("2024-03-04 09:10:00");
+    assert!(!user.is_expired(datetime!(2024-03-04 23:59:59 +5:30)));
+    assert!(!user.is_expired(datetime!(2024-03-05 00:29:59 UTC)));
+    assert!(user.is_expired(datetime!(2024-03-05 00:30:00 UTC)));
+}
+
+#[test]
+fn test_in_memory_store_returns_valid_session() {
+    let store = InMemorySessionStore::new();
+    store.save(&user_logged_in_at("2024-03-04 09:10:00")).unwrap();
+
+    let user = store.current(datetime!(2024-03-04 15:00:00 +5:30)).unwrap();
+    assert_eq!(user.access_token, "access_abc");
+}
+
+#[test]
+fn test_in_memory_store_requires_relogin_after_expiry() {
+    let store = InMemorySessionStore::new();
+    store.save(&user_logged_in_at("2024-03-04 09:10:00")).unwrap();
+
+    let result = store.current(datetime!(2024-03-05 06:00:00 +5:30));
+    assert!(matches!(result, Err(SessionError::ReloginRequired(_))));
+}
+
+#[test]
+fn test_in_memory_store_requires_relogin_when_empty() {
+    let store = InMemorySessionStore::new();
+    store.save(&user_logged_in_at("2024-03-04 09:10:00")).unwrap();
+    store.clear().unwrap();
+
+    let result = store.current(datetime!(2024-03-04 10:00:00 +5:30));
+    assert!(matches!(result, Err(SessionError::ReloginRequired(_))));
+}
+
+/// DB 15 of a local server: `REDIS_HOST=localhost cargo test --test user_session_lifecycle -- --ignored`
+fn redis_config() -> RedisConfig {
+    let env = |name: &str, default: &str| std::env::var(name).unwrap_or_else(|_| default.to_string());
+    RedisConfig {
+        host: env("REDIS_HOST", "localhost"),
+        port: env("REDIS_PORT", "6379").parse().unwrap(),
+        user: env("REDIS_USER", "default"),
+        password: env("REDIS_PASSWORD", ""),
+        db_num: "15".to_string(),
+    }
+}
+
+/// A session logged in `hours_ago`, with Kite's IST login time.
+fn user_logged_in_hours_ago(hours_ago: i64) -> User {
+    let login = (OffsetDateTime::now_utc() - time::Duration::hours(hours_ago)).to_offset(offset!(+5:30));
+    let login_time = login
+        .format(format_description!("[year]-[month]-[day] [hour]:[minute]:[second]"))
+        .unwrap();
+    user_logged_in_at(&login_time)
+}
+
+#[test]
+#[ignore]
+fn test_redis_store_save_load_and_expiry() {
+    let config = redis_config();
+    let store = RedisSessionStore::from_redis_config(&config, &format!("test_{}", std::process::id())).unwrap();
+    let mut conn = redis::Client::open(config.connection_string())
+        .unwrap()
+        .get_connection()
+        .unwrap();
+
+    store.clear().unwrap();
+    assert_eq!(store.load().unwrap(), None);
+
+    let user = user_logged_in_hours_ago(0);
+    store.save(&user).unwrap();
+    assert_eq!(store.load().unwrap(), Some(user.clone()));
+    assert_eq!(store.current(OffsetDateTime::now_utc()).unwrap(), user);
+
+    // Redis drops the key at the same 06:00 IST cutoff the session expires at.
+    let ttl: i64 = conn.ttl(store.key()).unwrap();
+    let expected = user.expires_at().unix_timestamp() - OffsetDateTime::now_utc().unix_timestamp();
+    assert!((expected - 5..=expected).contains(&ttl), "ttl {} vs {}", ttl, expected);
+
+    // A session past its cutoff is gone as soon as it is saved.
+    store.save(&user_logged_in_hours_ago(48)).unwrap();
+    assert_eq!(store.load().unwrap(), None);
+    assert!(matches!(
+        store.current(OffsetDateTime::now_utc()),
+        Err(SessionError::ReloginRequired(_))
+    ));
+
+    store.clear().unwrap();
+}
@@ -0,0 +1,148 @@
+use project_models::config_models::{
+    AccountRole, AccountSelector, Config, ZerodhaConfig, ZerodhaConfigError,
+};
+use project_models::data_models::generic_data::session_key;
+
+/// Parses a config without touching the global `Config` OnceCell.
+fn parse_config(content: &str) -> Config {
+    toml::from_str(content).unwrap()
+}
+
+const MULTI_ACCOUNT_CONFIG: &str = r#"
+[zerodha]
+default_account = "trading"
+
+[[zerodha.accounts]]
+id = "research"
+api_key = "research_key"
+api_secret = "research_secret"
+user_name = "RS0001"
+role = "data"
+
+[[zerodha.accounts]]
+id = "trading"
+api_key = "trading_key"
+api_secret = "trading_secret"
+user_name = "TR0001"
+role = "trading"
+"#;
+
+#[test]
+fn test_select_default_account() {
+    let config = parse_config(MULTI_ACCOUNT_CONFIG);
+    let zerodha = ZerodhaConfig::from_config(&config, AccountSelector::Default).unwrap();
+
+    assert_eq!(zerodha.account_id, "trading");
+    assert_eq!(zerodha.api_key, "trading_key");
+    assert_eq!(zerodha.role, AccountRole::Trading);
+}
+
+#[test]
+fn test_select_account_by_id_and_role() {
+    let config = parse_config(MULTI_ACCOUNT_CONFIG);
+
+    let by_id = ZerodhaConfig::from_config(&config, AccountSelector::Id("research".to_string())).unwrap();
+    assert_eq!(by_id.api_secret, "research_secret");
+
+    let by_role = ZerodhaConfig::from_config(&config, AccountSelector::Role(AccountRole::Data)).unwrap();
+    assert_eq!(by_role.account_id, "research");
+    assert_eq!(by_role.user_name, "RS0001");
+}
+
+#[test]
+fn test_unknown_account() {
+    let config = parse_config(MULTI_ACCOUNT_CONFIG);
+    let result = ZerodhaConfig::from_config(&config, AccountSelector::Id("missing".to_string()));
+    assert!(matches!(result, Err(ZerodhaConfigError::UnknownAccount(id)) if id == "missing"));
+}
+
+#[test]
+fn test_missing_role_is_named_as_configured() {
+    let config = parse_config(
+        r#"
+[zerodha]
+api_key = "key"
+api_secret = "secret"
+user_name = "AB0001"
+"#,
+    );
+    let result = ZerodhaConfig::from_config(&config, AccountSelector::Role(AccountRole::Data));
+    assert!(matches!(result, Err(ZerodhaConfigError::NoAccountForRole(role)) if role == "data"));
+    assert_eq!(AccountRole::Trading.to_string(), "trading");
+}
+
+#[test]
+fn test_duplicate_account_ids_are_rejected() {
+    let config = parse_config(
+        r#"
+        [[zerodha.accounts]]
+        id = "main"
+        api_key = "a"
+        api_secret = "b"
+        user_name = "c"
+
+        [[zerodha.accounts]]
+        id = "main"
+        api_key = "d"
+        api_secret = "e"
+        user_name = "f"
+        "#,
+    );
+    let result = ZerodhaConfig::from_config(&config, AccountSelector::Default);
+    assert!(matches!(result, Err(ZerodhaConfigError::DuplicateAccount(id)) if id == "main"));
+}
+
+#[test]
+fn test_legacy_single_account_section() {
+    let config = parse_config(
+        r#"
+        [zerodha]
+        api_key = "default_api_key"
+        api_secret = "default_api_secret"
+        user_name = "default_user"
+        "#,
+    );
+    let zerodha = ZerodhaConfig::from_config(&config, AccountSelector::Default).unwrap();
+
+    assert_eq!(zerodha.account_id, "default");
+    assert_eq!(zerodha.api_key, "default_api_key");
+    assert_eq!(zerodha.role, AccountRole::Trading);
+}
+
+#[test]
+fn test_account_typos_are_reported() {
+    let parse_error = |content: &str| toml::from_str::<Config>(content).map(|_| ()).unwrap_err().to_string();
+
+    let err = parse_error(
+        r#"
+        [[zerodha.accounts]]
+        id = "main"
+        api_key = "a"
+        api_secert = "b"
+        user_name = "c"
+        "#,
+    );
+    assert!(err.contains("missing field `api_secret`"), "{}", err);
+
+    let err = parse_error(
+        r#"
+        [zerodha]
+        api_key = "a"
+        user_name = "c"
+        "#,
+    );
+    assert!(err.contains("missing field `api_secret`"), "{}", err);
+}
+
+#[test]
+fn test_missing_zerodha_section() {
+    let config = parse_config("");
+    let result = ZerodhaConfig::from_config(&config, AccountSelector::Default);
+    assert!(matches!(result, Err(ZerodhaConfigError::MissingCredentials)));
+}
+
+#[test]
+fn test_sessions_are_keyed_per_account() {
+    assert_eq!(session_key("research"), "kite:session:research");
+    assert_ne!(session_key("research"), session_key("trading"));
+}